@@ -68,7 +68,7 @@ void Connection::logout()
     connection()->logout();
 }
 
-void Connection::createRoom(const QString &name, const QString &topic, const QString &alias)
+Task *Connection::createRoom(const QString &name, const QString &topic, const QString &alias)
 {
     auto options = sdk::room_create_options_new();
     if (!name.isEmpty()) {
@@ -82,7 +82,8 @@ void Connection::createRoom(const QString &name, const QString &topic, const QSt
     if (!alias.isEmpty()) {
         options->set_room_alias(stringToRust(alias));
     }
-    connection()->create_room(*options);
+    const auto token = stringFromRust(connection()->create_room(*options));
+    return new Task(token, this);
 }
 
 Room *Connection::room(const QString &id)
@@ -102,6 +103,21 @@ Task *Connection::setDisplayName(const QString &displayName)
     return new Task(token, this);
 }
 
+void Connection::cancelTask(Task *task)
+{
+    connection()->cancel_task(stringToRust(task->token()));
+}
+
+Task *Connection::joinRoom(const QString &idOrAlias, const QStringList &via)
+{
+    rust::Vec<rust::String> servers;
+    for (const auto &server : via) {
+        servers.push_back(stringToRust(server));
+    }
+    const auto token = stringFromRust(connection()->join_room(stringToRust(idOrAlias), std::move(servers)));
+    return new Task(token, this);
+}
+
 std::unique_ptr<RoomStream> Connection::roomStream()
 {
     return std::make_unique<RoomStream>(this);
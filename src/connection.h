@@ -32,12 +32,14 @@ public:
     Q_INVOKABLE void open(const QString &roomId);
     Q_INVOKABLE void logout();
 
-    Q_INVOKABLE void createRoom(const QString &name = {}, const QString &topic = {}, const QString &alias = {});
+    Q_INVOKABLE Task *createRoom(const QString &name = {}, const QString &topic = {}, const QString &alias = {});
 
     Q_INVOKABLE Quotient::Room *room(const QString &id);
     Q_INVOKABLE bool hasRoom(const QString &id);
 
     Q_INVOKABLE Task *setDisplayName(const QString &displayName);
+    Q_INVOKABLE void cancelTask(Task *task);
+    Q_INVOKABLE Task *joinRoom(const QString &idOrAlias, const QStringList &via = {});
 
     /*
      * @brief Get a room stream for the connection.
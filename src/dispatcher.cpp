@@ -6,14 +6,26 @@
 #include "sdk/include/callbacks.h"
 #include "utils.h"
 
+using namespace Qt::Literals::StringLiterals;
+
 void shim_connected(rust::String userId)
 {
     Q_EMIT Dispatcher::instance()->connected(stringFromRust(userId));
 }
 
-void shim_avatar_loaded(rust::String roomId, rust::Vec<std::uint8_t> _data)
+void shim_avatar_loaded(rust::String token, rust::Vec<std::uint8_t> _data)
+{
+    Q_EMIT Dispatcher::instance()->avatarLoaded(stringFromRust(token), QByteArray((const char *)_data.data(), _data.size()));
+}
+
+void shim_user_avatar_loaded(rust::String token, rust::Vec<std::uint8_t> _data)
 {
-    Q_EMIT Dispatcher::instance()->avatarLoaded(stringFromRust(roomId), QByteArray((const char *)_data.data(), _data.size()));
+    Q_EMIT Dispatcher::instance()->userAvatarLoaded(stringFromRust(token), QByteArray((const char *)_data.data(), _data.size()));
+}
+
+void shim_media_loaded(rust::String token, rust::String path, rust::Vec<std::uint8_t> _data)
+{
+    Q_EMIT Dispatcher::instance()->mediaLoaded(stringFromRust(token), stringFromRust(path), QByteArray((const char *)_data.data(), _data.size()));
 }
 
 void shim_rooms_changed(rust::String matrixId)
@@ -26,11 +38,111 @@ void shim_timeline_changed(rust::String matrix_id, rust::String room_id)
     Q_EMIT Dispatcher::instance()->timelineUpdate(stringFromRust(matrix_id), stringFromRust(room_id));
 }
 
+void shim_back_pagination_status_changed(rust::String matrixId, rust::String roomId, rust::String timelineId, std::uint8_t status)
+{
+    Q_EMIT Dispatcher::instance()->backPaginationStatusChanged(stringFromRust(matrixId), stringFromRust(roomId), stringFromRust(timelineId), status);
+}
+
 void shim_logged_out(rust::String matrixId)
 {
     Q_EMIT Dispatcher::instance()->loggedOut(stringFromRust(matrixId));
 }
 
+void shim_members_changed(rust::String matrixId, rust::String roomId)
+{
+    Q_EMIT Dispatcher::instance()->membersChanged(stringFromRust(matrixId), stringFromRust(roomId));
+}
+
+void shim_push_rules_changed(rust::String matrixId)
+{
+    Q_EMIT Dispatcher::instance()->pushRulesChanged(stringFromRust(matrixId));
+}
+
+static QVariantMap notificationToMap(const sdk::Notification &notification)
+{
+    return {
+        {u"roomId"_s, stringFromRust(notification.room_id())},
+        {u"eventId"_s, stringFromRust(notification.event_id())},
+        {u"roomName"_s, stringFromRust(notification.room_name())},
+        {u"senderId"_s, stringFromRust(notification.sender_id())},
+        {u"senderName"_s, stringFromRust(notification.sender_name())},
+        {u"body"_s, stringFromRust(notification.body())},
+        {u"isMention"_s, notification.is_mention()},
+        {u"isNoisy"_s, notification.is_noisy()},
+        {u"isInvite"_s, notification.is_invite()},
+        {u"isDirect"_s, notification.is_direct()},
+    };
+}
+
+void shim_notification_received(rust::String matrixId, rust::Box<sdk::Notification> notification)
+{
+    Q_EMIT Dispatcher::instance()->notificationReceived(stringFromRust(matrixId), notificationToMap(*notification));
+}
+
+void shim_push_resolved(rust::String token, rust::Box<sdk::Notification> notification)
+{
+    Q_EMIT Dispatcher::instance()->pushResolved(stringFromRust(token), notificationToMap(*notification));
+}
+
+void shim_pushers_loaded(rust::String token, rust::Vec<sdk::Pusher> pushers)
+{
+    QVariantList list;
+    for (const auto &pusher : pushers) {
+        list += QVariantMap{
+            {u"appId"_s, stringFromRust(pusher.app_id())},
+            {u"pushKey"_s, stringFromRust(pusher.push_key())},
+            {u"appDisplayName"_s, stringFromRust(pusher.app_display_name())},
+            {u"deviceDisplayName"_s, stringFromRust(pusher.device_display_name())},
+            {u"url"_s, stringFromRust(pusher.url())},
+            {u"format"_s, stringFromRust(pusher.format())},
+        };
+    }
+    Q_EMIT Dispatcher::instance()->pushersLoaded(stringFromRust(token), list);
+}
+
+void shim_space_hierarchy_loaded(rust::String token, rust::Vec<sdk::SpaceHierarchyRoom> rooms)
+{
+    QVariantList list;
+    for (const auto &room : rooms) {
+        list += QVariantMap{
+            {u"roomId"_s, stringFromRust(room.room_id())},
+            {u"parentId"_s, stringFromRust(room.parent_id())},
+            {u"name"_s, stringFromRust(room.name())},
+            {u"topic"_s, stringFromRust(room.topic())},
+            {u"avatarUrl"_s, stringFromRust(room.avatar_url())},
+            {u"canonicalAlias"_s, stringFromRust(room.canonical_alias())},
+            {u"numJoinedMembers"_s, QVariant::fromValue(room.num_joined_members())},
+            {u"joinRule"_s, room.join_rule()},
+            {u"isSpace"_s, room.is_space()},
+            {u"isSuggested"_s, room.is_suggested()},
+            {u"membership"_s, room.membership()},
+        };
+    }
+    Q_EMIT Dispatcher::instance()->spaceHierarchyLoaded(stringFromRust(token), list);
+}
+
+void shim_local_aliases_loaded(rust::String token, rust::Vec<rust::String> aliases)
+{
+    QStringList list;
+    for (const auto &alias : aliases) {
+        list += stringFromRust(alias);
+    }
+    Q_EMIT Dispatcher::instance()->localAliasesLoaded(stringFromRust(token), list);
+}
+
+void shim_typing_users_changed(rust::String matrixId, rust::String roomId, rust::Vec<rust::String> userIds, rust::Vec<rust::String> displayNames)
+{
+    QStringList ids;
+    for (const auto &id : userIds) {
+        ids += stringFromRust(id);
+    }
+    QStringList names;
+    for (const auto &name : displayNames) {
+        names += stringFromRust(name);
+    }
+    Q_EMIT Dispatcher::instance()->typingUsersChanged(stringFromRust(matrixId), stringFromRust(roomId), ids, names);
+}
+
 void shim_oidc_login_url_available(rust::String serverName, rust::String url)
 {
     Q_EMIT Dispatcher::instance()->oidcLoginUrlAvailable(stringFromRust(serverName), stringFromRust(url));
@@ -41,6 +153,16 @@ void task_done(rust::String token)
     Q_EMIT Dispatcher::instance()->taskDone(stringFromRust(token));
 }
 
+void task_failed(rust::String token, rust::String error)
+{
+    Q_EMIT Dispatcher::instance()->taskFailed(stringFromRust(token), stringFromRust(error));
+}
+
+void task_progress(rust::String token, std::uint64_t current, std::uint64_t total)
+{
+    Q_EMIT Dispatcher::instance()->taskProgress(stringFromRust(token), current, total);
+}
+
 Dispatcher::Dispatcher()
     : QObject(nullptr)
 {
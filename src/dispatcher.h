@@ -4,6 +4,7 @@
 #pragma once
 
 #include <QObject>
+#include <QVariantMap>
 
 /* Dispatcher is used internally to redistribute "callbacks" from Rust to C++.
  * It's the only nice way i have come up with for passing the result of async
@@ -24,12 +25,25 @@ public:
 
 Q_SIGNALS:
     void connected(const QString &matrixId);
-    void avatarLoaded(const QString &roomId, const QByteArray &data);
+    void avatarLoaded(const QString &token, const QByteArray &data);
+    void userAvatarLoaded(const QString &token, const QByteArray &data);
+    void mediaLoaded(const QString &token, const QString &path, const QByteArray &data);
     void roomsUpdate(const QString &matrixId);
     void timelineUpdate(const QString &matrix_id, const QString &room_id);
+    void backPaginationStatusChanged(const QString &matrixId, const QString &roomId, const QString &timelineId, std::uint8_t status);
     void loggedOut(const QString &matrixId);
+    void membersChanged(const QString &matrixId, const QString &roomId);
+    void pushRulesChanged(const QString &matrixId);
+    void notificationReceived(const QString &matrixId, const QVariantMap &notification);
+    void pushResolved(const QString &token, const QVariantMap &notification);
+    void pushersLoaded(const QString &token, const QVariantList &pushers);
+    void spaceHierarchyLoaded(const QString &token, const QVariantList &rooms);
+    void localAliasesLoaded(const QString &token, const QStringList &aliases);
+    void typingUsersChanged(const QString &matrixId, const QString &roomId, const QStringList &userIds, const QStringList &displayNames);
     void oidcLoginUrlAvailable(const QString &serverName, const QString &url);
     void taskDone(const QString &token);
+    void taskFailed(const QString &token, const QString &error);
+    void taskProgress(const QString &token, quint64 current, quint64 total);
 
 private:
     Dispatcher();
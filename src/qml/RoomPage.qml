@@ -40,10 +40,14 @@ Kirigami.Page {
                 required property string eventId
                 required property string body
                 required property int index
-                required property string timestamp
+                required property date timestamp
+                required property int kind
 
                 // width: parent.width
-                text: timestamp + " " + body
+                // Read markers and the start of the timeline don't have a timestamp
+                text: kind === 0 ? timestamp.toLocaleTimeString(Qt.locale(), Locale.ShortFormat) + " " + body
+                    : kind === 1 ? timestamp.toLocaleDateString(Qt.locale())
+                    : ""
             }
         }
     }
@@ -16,16 +16,17 @@ QQuickImageResponse *RoomAvatarImageProvider::requestImageResponse(const QString
     return new RoomAvatarImageResponse(id, requestedSize, m_connection.get());
 }
 
-RoomAvatarImageResponse::RoomAvatarImageResponse(const QString &id, const QSize &, Connection *connection)
+RoomAvatarImageResponse::RoomAvatarImageResponse(const QString &id, const QSize &requestedSize, Connection *connection)
 {
-    connection->connection()->room_avatar(stringToRust(id));
-    connect(Dispatcher::instance(), &Dispatcher::avatarLoaded, this, [id, this](const auto &roomId, const QByteArray &data) {
-        if (id != roomId) {
+    // Connect before requesting the avatar, so that an answer arriving right away isn't missed
+    connect(Dispatcher::instance(), &Dispatcher::avatarLoaded, this, [this](const auto &token, const QByteArray &data) {
+        if (token != m_token) {
             return;
         }
         m_image = QImage::fromData(data);
         Q_EMIT finished();
     });
+    m_token = stringFromRust(connection->connection()->room_avatar(stringToRust(id), std::max(requestedSize.width(), 0), std::max(requestedSize.height(), 0)));
 }
 
 Connection *RoomAvatarImageProvider::connection() const
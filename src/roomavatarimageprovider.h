@@ -49,4 +49,5 @@ public:
 
 private:
     QImage m_image;
+    QString m_token;
 };
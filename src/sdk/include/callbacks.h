@@ -9,12 +9,28 @@
 
 namespace sdk {
 class RoomListItem;
+class Notification;
+class Pusher;
+class SpaceHierarchyRoom;
 }
 
 void shim_connected(rust::String matrixId);
 void shim_rooms_changed(rust::String matrixId);
 void shim_timeline_changed(rust::String matrixId, rust::String roomId);
-void shim_avatar_loaded(rust::String roomId, rust::Vec<std::uint8_t> data);
+void shim_back_pagination_status_changed(rust::String matrixId, rust::String roomId, rust::String timelineId, std::uint8_t status);
+void shim_avatar_loaded(rust::String token, rust::Vec<std::uint8_t> data);
+void shim_user_avatar_loaded(rust::String token, rust::Vec<std::uint8_t> data);
+void shim_media_loaded(rust::String token, rust::String path, rust::Vec<std::uint8_t> data);
 void shim_logged_out(rust::String matrixId);
+void shim_members_changed(rust::String matrixId, rust::String roomId);
+void shim_push_rules_changed(rust::String matrixId);
+void shim_notification_received(rust::String matrixId, rust::Box<sdk::Notification> notification);
+void shim_push_resolved(rust::String token, rust::Box<sdk::Notification> notification);
+void shim_pushers_loaded(rust::String token, rust::Vec<sdk::Pusher> pushers);
+void shim_space_hierarchy_loaded(rust::String token, rust::Vec<sdk::SpaceHierarchyRoom> rooms);
+void shim_local_aliases_loaded(rust::String token, rust::Vec<rust::String> aliases);
+void shim_typing_users_changed(rust::String matrixId, rust::String roomId, rust::Vec<rust::String> userIds, rust::Vec<rust::String> displayNames);
 void shim_oidc_login_url_available(rust::String serverName, rust::String url);
-void task_done(rust::String token);
\ No newline at end of file
+void task_done(rust::String token);
+void task_failed(rust::String token, rust::String error);
+void task_progress(rust::String token, std::uint64_t current, std::uint64_t total);
\ No newline at end of file
@@ -0,0 +1,113 @@
+// SPDX-FileCopyrightText: 2026 Tobias Fella <tobias.fella@kde.org>
+// SPDX-License-Identifier: LGPL-2.0-or-later
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use matrix_sdk::attachment::{
+    AttachmentConfig, AttachmentInfo, BaseAudioInfo, BaseFileInfo, BaseImageInfo, BaseVideoInfo,
+    Thumbnail,
+};
+use matrix_sdk::ruma::UInt;
+use mime::Mime;
+
+/// Metadata for sending a file. Everything is optional; the type of message is chosen based on the file's MIME type.
+#[derive(Clone, Default)]
+pub struct AttachmentOptions {
+    caption: Option<String>,
+    width: Option<u32>,
+    height: Option<u32>,
+    duration: Option<Duration>,
+    thumbnail: Option<(PathBuf, u32, u32)>,
+    voice_message: bool,
+}
+
+impl AttachmentOptions {
+    pub fn set_caption(&mut self, caption: String) {
+        self.caption = Some(caption);
+    }
+
+    /// Dimensions of an image or video.
+    pub fn set_dimensions(&mut self, width: u32, height: u32) {
+        self.width = Some(width);
+        self.height = Some(height);
+    }
+
+    /// Duration of an audio or video file.
+    pub fn set_duration(&mut self, duration_ms: u64) {
+        self.duration = Some(Duration::from_millis(duration_ms));
+    }
+
+    /// Thumbnail for an image or video, generated by the caller.
+    pub fn set_thumbnail(&mut self, path: String, width: u32, height: u32) {
+        self.thumbnail = Some((PathBuf::from(path), width, height));
+    }
+
+    /// Send an audio file as a voice message.
+    pub fn set_voice_message(&mut self, voice_message: bool) {
+        self.voice_message = voice_message;
+    }
+
+    pub(crate) async fn config(
+        &self,
+        content_type: &Mime,
+        size: usize,
+    ) -> std::io::Result<AttachmentConfig> {
+        let thumbnail = match &self.thumbnail {
+            Some((path, width, height)) => Some(thumbnail(path, *width, *height).await?),
+            None => None,
+        };
+        Ok(AttachmentConfig::new()
+            .info(self.info(content_type, size))
+            .thumbnail(thumbnail)
+            .caption(self.caption.clone()))
+    }
+
+    fn info(&self, content_type: &Mime, size: usize) -> AttachmentInfo {
+        let size = Some(UInt::new_saturating(size as u64));
+        let width = self.width.map(UInt::from);
+        let height = self.height.map(UInt::from);
+        match content_type.type_() {
+            mime::IMAGE => AttachmentInfo::Image(BaseImageInfo {
+                width,
+                height,
+                size,
+                ..Default::default()
+            }),
+            mime::VIDEO => AttachmentInfo::Video(BaseVideoInfo {
+                duration: self.duration,
+                width,
+                height,
+                size,
+                ..Default::default()
+            }),
+            mime::AUDIO if self.voice_message => AttachmentInfo::Voice {
+                audio_info: BaseAudioInfo {
+                    duration: self.duration,
+                    size,
+                },
+                waveform: None,
+            },
+            mime::AUDIO => AttachmentInfo::Audio(BaseAudioInfo {
+                duration: self.duration,
+                size,
+            }),
+            _ => AttachmentInfo::File(BaseFileInfo { size }),
+        }
+    }
+}
+
+async fn thumbnail(path: &Path, width: u32, height: u32) -> std::io::Result<Thumbnail> {
+    let data = tokio::fs::read(path).await?;
+    Ok(Thumbnail {
+        size: UInt::new_saturating(data.len() as u64),
+        data,
+        content_type: mime_guess::from_path(path).first_or(mime::IMAGE_JPEG),
+        width: UInt::from(width),
+        height: UInt::from(height),
+    })
+}
+
+pub fn attachment_options_new() -> Box<AttachmentOptions> {
+    Box::new(AttachmentOptions::default())
+}
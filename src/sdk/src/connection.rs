@@ -5,42 +5,54 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 use matrix_sdk::authentication::oauth::{ClientRegistrationData, OAuthSession, UrlOrQuery};
 use matrix_sdk::Client;
-use matrix_sdk::media::MediaFormat;
+use matrix_sdk::media::{MediaFormat, MediaRequestParameters, MediaThumbnailSettings};
+use matrix_sdk::notification_settings::NotificationSettingsError;
 use matrix_sdk::ruma::api::client::error::StandardErrorBody;
-use matrix_sdk::ruma::{RoomId, UserId};
+use matrix_sdk::ruma::api::client::push::{get_pushers, HttpPusherData, PusherInit, PusherKind};
+use matrix_sdk::HttpError;
+use matrix_sdk::ruma::{
+    EventId, OwnedEventId, OwnedServerName, RoomId, RoomOrAliasId, ServerName, UserId,
+};
 use tokio::runtime::Runtime;
-use crate::{ffi::ffi, OidcSession, RoomCreateOptions, Rooms, SessionData, Timeline};
+use crate::{ffi::ffi, BackPaginationStatus, OidcSession, Rooms, SessionData, Timeline};
+use crate::media::{self, MediaCache};
+use crate::notifications;
+use crate::notificationsettings;
+use crate::pushers::{self, Pusher};
+use crate::pushrules;
+use crate::redaction::Redactions;
 use crate::room::Room;
-use std::sync::{Arc, RwLock};
+use crate::roomcreateoptions::{room_create_options_new, RoomCreateOptions};
+use crate::sendqueue::{self, PendingEvent};
+use crate::spacehierarchy::SpaceHierarchy;
+use crate::task::{self, token, CancellableTasks, Subscription};
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
 use eyeball_im::VectorDiff;
 use matrix_sdk::authentication::oauth::registration::{ApplicationType, ClientMetadata, Localized, OAuthGrantType};
 use matrix_sdk::reqwest::Url;
 use matrix_sdk::ruma::serde::Raw;
+use matrix_sdk_ui::notification_client::{NotificationClient, NotificationProcessSetup};
 use matrix_sdk_ui::sync_service::SyncService;
-use matrix_sdk_ui::timeline::TimelineBuilder;
-use rand::distributions::Alphanumeric;
-use rand::Rng;
+use matrix_sdk_ui::timeline::{DateDividerMode, TimelineBuilder, TimelineFocus};
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::net::TcpSocket;
+use tokio::sync::broadcast::error::RecvError;
 use tokio_stream::StreamExt;
 
 pub(crate) struct Connection {
     pub rt: Runtime,
     pub client: Client,
+    pub tasks: CancellableTasks,
+    pub media_cache: Arc<MediaCache>,
+    /// 0: Daily, 1: Monthly
+    pub date_divider_mode: AtomicU8,
 }
 
 fn sqlite_passphrase<'a>() -> Option<&'a str> {
     None //TODO
 }
 
-fn token() -> String {
-    rand::thread_rng()
-        .sample_iter(&Alphanumeric)
-        .take(16)
-        .map(char::from)
-        .collect()
-}
-
 fn state_parent() -> PathBuf {
     dirs::state_dir()
         .unwrap()
@@ -53,6 +65,20 @@ fn state_dir(matrix_id: &String) -> PathBuf {
         .join(matrix_id)
 }
 
+fn client_matrix_id(client: &Client) -> String {
+    client
+        .user_id()
+        .map(|it| it.to_string())
+        .unwrap_or_default()
+}
+
+pub(crate) fn server_names(names: Vec<String>) -> Result<Vec<OwnedServerName>, String> {
+    names
+        .into_iter()
+        .map(|name| ServerName::parse(name).map_err(|error| error.to_string()))
+        .collect()
+}
+
 fn needs_resolving(matrix_id: String) -> Option<String> {
     let file = state_parent().join("unresolved.json");
     if !std::fs::exists(&file).unwrap() {
@@ -124,14 +150,41 @@ impl Connection {
             }
             ffi::shim_connected(matrix_id.to_string());
         });
-        Box::new(Connection { rt, client })
+        Box::new(Connection {
+            rt,
+            client,
+            tasks: Default::default(),
+            media_cache: MediaCache::shared(),
+            date_divider_mode: AtomicU8::new(0),
+        })
     }
 
-    pub(crate) fn timeline_paginate_back(&self, timeline: &Timeline) {
-        let timeline = timeline.timeline.clone();
-        self.rt.spawn(async move {
-            timeline.write().await.paginate_backwards(20).await.unwrap();
+    /// Load `num_events` older events into the timeline.
+    ///
+    /// If a back pagination is already running for this timeline, returns its token instead of starting another one.
+    pub(crate) fn timeline_paginate_back(&self, timeline: &Timeline, num_events: u16) -> String {
+        let mut running = timeline.back_pagination.lock().unwrap();
+        if let Some(token) = running.as_ref() {
+            return token.clone();
+        }
+        let timeline = timeline.clone();
+        let token = task::spawn(&self.rt, async move {
+            timeline.set_back_pagination_status(BackPaginationStatus::Paginating);
+            let result = timeline
+                .timeline
+                .read()
+                .await
+                .paginate_backwards(num_events)
+                .await;
+            *timeline.back_pagination.lock().unwrap() = None;
+            timeline.set_back_pagination_status(match result {
+                Ok(true) => BackPaginationStatus::HitStart,
+                _ => BackPaginationStatus::Idle,
+            });
+            result
         });
+        *running = Some(token.clone());
+        token
     }
 
     pub(crate) fn session(&self) -> String {
@@ -181,7 +234,13 @@ impl Connection {
                 .unwrap();
             ffi::shim_connected(matrix_id);
         });
-        Box::new(Connection { rt, client })
+        Box::new(Connection {
+            rt,
+            client,
+            tasks: Default::default(),
+            media_cache: MediaCache::shared(),
+            date_divider_mode: AtomicU8::new(0),
+        })
     }
 
     pub(crate) fn init_oidc(server_name: String) -> Box<Connection> {
@@ -245,68 +304,214 @@ impl Connection {
             add_to_unresolved(client.user_id().unwrap().to_string(), token);
             ffi::shim_connected(server_name);
         });
-        Box::new(Connection { rt, client })
+        Box::new(Connection {
+            rt,
+            client,
+            tasks: Default::default(),
+            media_cache: MediaCache::shared(),
+            date_divider_mode: AtomicU8::new(0),
+        })
     }
 
     pub(crate) fn timeline(&self, room_id: String) -> Box<Timeline> {
+        self.build_timeline(room_id, TimelineFocus::Live { hide_threaded_events: false }, None)
+            .unwrap()
+    }
+
+    /// Open a timeline containing only the thread started by `root_event_id`.
+    ///
+    /// Messages sent through this timeline are sent as replies in the thread.
+    pub(crate) fn thread_timeline(
+        &self,
+        room_id: String,
+        root_event_id: String,
+    ) -> Result<Box<Timeline>, String> {
+        let root_event_id = EventId::parse(root_event_id).map_err(|error| error.to_string())?;
+        self.build_timeline(
+            room_id,
+            TimelineFocus::Thread {
+                root_event_id: root_event_id.clone(),
+            },
+            Some(root_event_id),
+        )
+    }
+
+    fn build_timeline(
+        &self,
+        room_id: String,
+        focus: TimelineFocus,
+        thread_root: Option<OwnedEventId>,
+    ) -> Result<Box<Timeline>, String> {
         let client = self.client.clone();
         let matrix_id = client
             .user_id()
             .map(|it| it.to_string())
             .unwrap_or("".to_string());
-        let room_id = RoomId::parse(room_id).unwrap();
-        let room = client.get_room(&room_id).unwrap();
+        let room_id = RoomId::parse(room_id).map_err(|error| error.to_string())?;
+        let room = client
+            .get_room(&room_id)
+            .ok_or_else(|| format!("Unknown room {room_id}"))?;
+        let event_focused = matches!(focus, TimelineFocus::Event { .. });
+        let date_divider_mode = match self.date_divider_mode.load(Ordering::Relaxed) {
+            1 => DateDividerMode::Monthly,
+            _ => DateDividerMode::Daily,
+        };
+        let mode = date_divider_mode.clone();
+        let redactions = Redactions::default();
+        let redaction_handler = redactions.watch(&room);
         let (timeline, items, stream) = self.rt.block_on(async move {
-            let timeline = TimelineBuilder::new(&room).build().await.unwrap();
+            let timeline = TimelineBuilder::new(&room)
+                .with_focus(focus)
+                .with_date_divider_mode(mode)
+                .build()
+                .await
+                .map_err(|error| error.to_string())?;
             let (items, stream) = timeline.subscribe().await;
-            (timeline, items, stream)
-        });
+            Ok::<_, String>((timeline, items, stream))
+        })?;
 
         let timeline = Box::new(Timeline {
             queue: Arc::new(RwLock::new(vec![])),
             timeline: Arc::new(tokio::sync::RwLock::new(timeline)),
+            thread_root,
+            date_divider_mode,
+            event_focused: Arc::new(AtomicBool::new(event_focused)),
+            forwarder: Default::default(),
+            back_pagination: Arc::new(Mutex::new(None)),
+            back_pagination_status: Arc::new(AtomicU8::new(BackPaginationStatus::Idle as u8)),
+            forward_pagination: Arc::new(Mutex::new(None)),
+            redactions,
+            _redaction_handler: Arc::new(redaction_handler),
+            id: token(),
+            matrix_id,
+            room_id: room_id.to_string(),
         });
-        let queue = timeline.queue.clone();
-        self.rt.spawn(async move {
-            tokio::pin!(stream);
-
-            let mxid = matrix_id.clone();
+        timeline.forward(self.rt.handle(), VectorDiff::Append { values: items }, stream);
+        Ok(timeline)
+    }
 
-            queue
-                .write()
-                .unwrap()
-                .push(VectorDiff::Append { values: items });
-            ffi::shim_timeline_changed(mxid, room_id.to_string());
+    /// Set how timelines opened afterwards insert date dividers.
+    ///
+    /// 0: Between events on different days, 1: Between events in different months
+    pub(crate) fn set_date_divider_mode(&self, mode: u8) {
+        self.date_divider_mode.store(mode, Ordering::Relaxed);
+    }
 
-            loop {
-                let matrix_id = matrix_id.clone();
-                let room_id = room_id.to_string();
-                let Some(entries) = stream.next().await else {
-                    continue; //TODO or return?
-                };
+    /// Open a timeline around the event `event_id`, e.g. for jumping to a permalink.
+    ///
+    /// `num_context_events` is the number of events to load before and after the event. The timeline switches to
+    /// the live timeline once forward pagination reaches the end of the room.
+    pub(crate) fn event_timeline(
+        &self,
+        room_id: String,
+        event_id: String,
+        num_context_events: u16,
+    ) -> Result<Box<Timeline>, String> {
+        let target = EventId::parse(event_id).map_err(|error| error.to_string())?;
+        self.build_timeline(
+            room_id,
+            TimelineFocus::Event {
+                target,
+                num_context_events,
+                hide_threaded_events: false,
+            },
+            None,
+        )
+    }
 
-                for entry in entries {
-                    queue.write().unwrap().push(entry);
+    /// Load `num_events` newer events into a focused timeline.
+    ///
+    /// If a forward pagination is already running for this timeline, returns its token instead of starting another
+    /// one.
+    pub(crate) fn timeline_paginate_forward(&self, timeline: &Timeline, num_events: u16) -> String {
+        let mut running = timeline.forward_pagination.lock().unwrap();
+        if let Some(token) = running.as_ref() {
+            return token.clone();
+        }
+        let timeline = timeline.clone();
+        let rt = self.rt.handle().clone();
+        let token = task::spawn(&self.rt, async move {
+            let result = timeline
+                .timeline
+                .read()
+                .await
+                .paginate_forwards(num_events)
+                .await;
+            let result = match result {
+                Ok(true) if timeline.event_focused.load(Ordering::Relaxed) => {
+                    timeline.switch_to_live(&rt).await
                 }
-                ffi::shim_timeline_changed(matrix_id, room_id);
+                result => result.map(|_| ()),
+            };
+            *timeline.forward_pagination.lock().unwrap() = None;
+            result
+        });
+        *running = Some(token.clone());
+        token
+    }
+
+    /// Load the avatar of a room, as thumbnail if `width` and `height` are not 0.
+    ///
+    /// Rooms without an avatar use the avatar of one of their heroes. The result is reported through
+    /// `shim_avatar_loaded` with the returned token; the data is empty if there is no avatar.
+    pub(crate) fn room_avatar(&self, room_id: String, width: u32, height: u32) -> String {
+        let client = self.client.clone();
+        let token = token();
+        let token_clone = token.clone();
+        self.rt.spawn(async move {
+            let data = async {
+                let room = client.get_room(&RoomId::parse(&room_id).ok()?)?;
+                let url = room.avatar_url().or_else(|| {
+                    room.heroes()
+                        .into_iter()
+                        .find_map(|hero| hero.avatar_url)
+                })?;
+                media::avatar(&client, url, width, height).await
             }
+            .await
+            .unwrap_or_default();
+            ffi::shim_avatar_loaded(token_clone, data);
         });
-        timeline
+        token
     }
 
-    pub(crate) fn room_avatar(&self, room_id: String) {
-        let client = self.rt.block_on(async { self.client.clone() });
+    /// Load the avatar of a user, as thumbnail if `width` and `height` are not 0.
+    ///
+    /// Uses the user's avatar in the room `room_id`, or in any joined room shared with them if `room_id` is empty.
+    /// The result is reported through `shim_user_avatar_loaded` with the returned token; the data is empty if there is
+    /// no avatar.
+    pub(crate) fn user_avatar(
+        &self,
+        user_id: String,
+        room_id: String,
+        width: u32,
+        height: u32,
+    ) -> String {
+        let client = self.client.clone();
+        let token = token();
+        let token_clone = token.clone();
         self.rt.spawn(async move {
-            let room_id = RoomId::parse(room_id).unwrap();
-            let data = client
-                .get_room(&room_id)
-                .unwrap()
-                .avatar(MediaFormat::File)
-                .await
-                .unwrap()
-                .unwrap_or("".into());
-            ffi::shim_avatar_loaded(room_id.to_string(), data);
+            let data = async {
+                let user_id = UserId::parse(&user_id).ok()?;
+                let rooms = if room_id.is_empty() {
+                    client.joined_rooms()
+                } else {
+                    vec![client.get_room(&RoomId::parse(&room_id).ok()?)?]
+                };
+                let mut url = None;
+                for room in rooms {
+                    if let Ok(Some(member)) = room.get_member_no_sync(&user_id).await {
+                        url = member.avatar_url().map(|url| url.to_owned());
+                        break;
+                    }
+                }
+                media::avatar(&client, url?, width, height).await
+            }
+            .await
+            .unwrap_or_default();
+            ffi::shim_user_avatar_loaded(token_clone, data);
         });
+        token
     }
 
     pub(crate) fn device_id(&self) -> String {
@@ -342,7 +547,8 @@ impl Connection {
                 .user_id()
                 .map(|it| it.to_string())
                 .unwrap_or("".to_string());
-            let sync_service = SyncService::builder(client).build().await.unwrap();
+            let sync_service = Arc::new(SyncService::builder(client.clone()).build().await.unwrap());
+            notifications::listen(client, sync_service.clone()).await;
             let service = sync_service.room_list_service();
             sync_service.start().await;
             let room_list = service.all_rooms().await.unwrap();
@@ -361,8 +567,10 @@ impl Connection {
         rooms
     }
 
+    /// Log out and clear the media cache, since it contains decrypted media.
     pub(crate) fn logout(&self) {
         let client = self.client.clone();
+        let media_cache = self.media_cache.clone();
         self.rt.spawn(async move {
             let result = client.matrix_auth().logout().await;
             use http::status::StatusCode;
@@ -382,11 +590,13 @@ impl Connection {
                                                              }),
                                          ..
                                      })) => {
+                        media_cache.clear().await;
                         ffi::shim_logged_out(client.user_id().unwrap().to_string());
                     }
                     _ => {}
                 },
                 Ok(..) => {
+                    media_cache.clear().await;
                     ffi::shim_logged_out(client.user_id().unwrap().to_string());
                 }
                 x => eprintln!("Error logging out: {:?}", x),
@@ -394,12 +604,16 @@ impl Connection {
         });
     }
 
-    pub(crate) fn create_room(&self, room_create_options: &RoomCreateOptions) {
+    /// Create a room; fails if any of the options were invalid.
+    pub(crate) fn create_room(&self, room_create_options: &RoomCreateOptions) -> String {
         let client = self.client.clone();
-        let options = room_create_options.0.clone();
-        self.rt.spawn(async move {
-            client.create_room(options).await.unwrap();
-        });
+        let request = room_create_options.request();
+        task::spawn(&self.rt, async move {
+            client
+                .create_room(request?)
+                .await
+                .map_err(|error| error.to_string())
+        })
     }
 
     pub(crate) fn room(&self, id: String) -> Box<Room> {
@@ -412,6 +626,369 @@ impl Connection {
         })
     }
 
+    /// Cancel a task that was started as cancellable, e.g. sending an attachment.
+    pub(crate) fn cancel_task(&self, token: String) {
+        self.tasks.cancel(&token);
+    }
+
+    /// Fetch the media `source`, given as `mxc://` URI or JSON of a `MediaSource`, into the media cache.
+    ///
+    /// The path of the cached file is reported through `shim_media_loaded`, along with the file's content if
+    /// `with_data` is set. Encrypted media is decrypted. The task can be cancelled.
+    pub(crate) fn media_content(&self, source: String, with_data: bool) -> String {
+        self.fetch_media(source, MediaFormat::File, with_data)
+    }
+
+    /// Like [`Connection::media_content`], but fetches a thumbnail of the given size.
+    ///
+    /// `method` is 0 for scaling and 1 for cropping the thumbnail to the size.
+    pub(crate) fn media_thumbnail(
+        &self,
+        source: String,
+        width: u32,
+        height: u32,
+        method: u8,
+        with_data: bool,
+    ) -> String {
+        let settings =
+            MediaThumbnailSettings::with_method(media::method(method), width.into(), height.into());
+        self.fetch_media(source, MediaFormat::Thumbnail(settings), with_data)
+    }
+
+    fn fetch_media(&self, source: String, format: MediaFormat, with_data: bool) -> String {
+        let client = self.client.clone();
+        let cache = self.media_cache.clone();
+        self.tasks.spawn(&self.rt, move |token| async move {
+            let request = MediaRequestParameters {
+                source: media::media_source(&source)?,
+                format,
+            };
+            let path = cache
+                .fetch(&client, &request)
+                .await
+                .map_err(|error| error.to_string())?;
+            let data = if with_data {
+                tokio::fs::read(&path)
+                    .await
+                    .map_err(|error| error.to_string())?
+            } else {
+                Default::default()
+            };
+            ffi::shim_media_loaded(token, path.to_string_lossy().to_string(), data);
+            Ok::<(), String>(())
+        })
+    }
+
+    /// Set the maximum size of the media cache in bytes, evicting files if necessary.
+    ///
+    /// The media cache is shared by all accounts, so this applies to all of them, as does clearing it.
+    pub(crate) fn set_media_cache_size(&self, max_size: u64) {
+        self.media_cache.set_max_size(max_size);
+        let cache = self.media_cache.clone();
+        self.rt.spawn(async move { cache.evict(None).await });
+    }
+
+    pub(crate) fn clear_media_cache(&self) {
+        let cache = self.media_cache.clone();
+        self.rt.spawn(async move { cache.clear().await });
+    }
+
+    /// The notification mode used for rooms without their own mode.
+    ///
+    /// There are separate defaults for encrypted and unencrypted rooms and for group and one-to-one rooms. In encrypted
+    /// rooms, "mentions and keywords only" only applies if [`Connection::can_push_encrypted_events`].
+    /// 0: All messages, 1: Mentions and keywords only, 2: Mute
+    pub(crate) fn default_notification_mode(&self, encrypted: bool, one_to_one: bool) -> u8 {
+        let client = self.client.clone();
+        let mode = self.rt.block_on(async move {
+            client
+                .notification_settings()
+                .await
+                .get_default_room_notification_mode(encrypted.into(), one_to_one.into())
+                .await
+        });
+        notificationsettings::mode_to_u8(mode)
+    }
+
+    /// Set the notification mode used for rooms without their own mode. See [`Connection::default_notification_mode`].
+    pub(crate) fn set_default_notification_mode(
+        &self,
+        encrypted: bool,
+        one_to_one: bool,
+        mode: u8,
+    ) -> String {
+        let client = self.client.clone();
+        task::spawn(&self.rt, async move {
+            client
+                .notification_settings()
+                .await
+                .set_default_room_notification_mode(
+                    encrypted.into(),
+                    one_to_one.into(),
+                    notificationsettings::mode_from_u8(mode),
+                )
+                .await
+        })
+    }
+
+    /// Whether the server pushes encrypted events to this device, so that it can decide whether they mention the user.
+    ///
+    /// Without this, "mentions and keywords only" has no effect in encrypted rooms.
+    pub(crate) fn can_push_encrypted_events(&self) -> bool {
+        let client = self.client.clone();
+        self.rt.block_on(async move {
+            client
+                .notification_settings()
+                .await
+                .can_push_encrypted_event_to_device()
+                .await
+        })
+    }
+
+    /// Whether a default push rule is enabled. See [`pushrules::rules`] for the values of `rule`.
+    ///
+    /// The setting is enabled if any of its rules that exist on the server is enabled.
+    pub(crate) fn is_push_rule_enabled(&self, rule: u8) -> bool {
+        let client = self.client.clone();
+        self.rt.block_on(async move {
+            let settings = client.notification_settings().await;
+            for (kind, rule_id) in pushrules::rules(rule) {
+                if let Ok(true) = settings.is_push_rule_enabled(kind, rule_id).await {
+                    return true;
+                }
+            }
+            false
+        })
+    }
+
+    /// Enable or disable a default push rule. See [`pushrules::rules`] for the values of `rule`.
+    ///
+    /// Fails if the server has none of the setting's rules.
+    pub(crate) fn set_push_rule_enabled(&self, rule: u8, enabled: bool) -> String {
+        let client = self.client.clone();
+        task::spawn(&self.rt, async move {
+            let settings = client.notification_settings().await;
+            let rules = pushrules::rules(rule);
+            let rule_ids: Vec<String> = rules.iter().map(|(_, rule_id)| rule_id.clone()).collect();
+            let mut found = false;
+            for (kind, rule_id) in rules {
+                match settings.set_push_rule_enabled(kind, rule_id, enabled).await {
+                    Ok(()) => found = true,
+                    // Servers don't have all default rules, e.g. older ones lack the newer mention rules
+                    Err(NotificationSettingsError::RuleNotFound(_)) => {}
+                    Err(error) => return Err(error),
+                }
+            }
+            if !found {
+                return Err(NotificationSettingsError::RuleNotFound(rule_ids.join(", ")));
+            }
+            Ok::<(), NotificationSettingsError>(())
+        })
+    }
+
+    /// The keywords that trigger a notification.
+    pub(crate) fn keywords(&self) -> Vec<String> {
+        let client = self.client.clone();
+        self.rt.block_on(async move {
+            client
+                .notification_settings()
+                .await
+                .enabled_keywords()
+                .await
+                .into_iter()
+                .collect()
+        })
+    }
+
+    pub(crate) fn add_keyword(&self, keyword: String) -> String {
+        let client = self.client.clone();
+        task::spawn(&self.rt, async move {
+            client
+                .notification_settings()
+                .await
+                .add_keyword(keyword)
+                .await
+        })
+    }
+
+    pub(crate) fn remove_keyword(&self, keyword: String) -> String {
+        let client = self.client.clone();
+        task::spawn(&self.rt, async move {
+            client
+                .notification_settings()
+                .await
+                .remove_keyword(&keyword)
+                .await
+        })
+    }
+
+    /// Get notified through `shim_push_rules_changed` when the push rules change, e.g. on another device.
+    pub(crate) fn subscribe_to_push_rules(&self) -> Box<Subscription> {
+        let client = self.client.clone();
+        let handle = self.rt.spawn(async move {
+            let matrix_id = client_matrix_id(&client);
+            let settings = client.notification_settings().await;
+            let mut receiver = settings.subscribe_to_changes();
+            loop {
+                match receiver.recv().await {
+                    // Missed notifications don't matter, the push rules changed either way
+                    Ok(()) | Err(RecvError::Lagged(_)) => {
+                        ffi::shim_push_rules_changed(matrix_id.clone())
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        });
+        Box::new(Subscription(handle.abort_handle()))
+    }
+
+    /// Register a pusher for this device with an HTTP push gateway, e.g. a UnifiedPush distributor.
+    ///
+    /// `format` is the format of the pushed notifications, e.g. `event_id_only`; empty for the full format.
+    pub(crate) fn register_pusher(
+        &self,
+        app_id: String,
+        push_key: String,
+        url: String,
+        format: String,
+        app_display_name: String,
+        device_display_name: String,
+        lang: String,
+    ) -> String {
+        let client = self.client.clone();
+        task::spawn(&self.rt, async move {
+            let mut data = HttpPusherData::new(url);
+            data.format = pushers::format(format);
+            let pusher = PusherInit {
+                ids: pushers::ids(app_id, push_key),
+                kind: PusherKind::Http(data),
+                app_display_name,
+                device_display_name,
+                profile_tag: None,
+                lang,
+            };
+            client.pusher().set(pusher.into()).await
+        })
+    }
+
+    /// Load the pushers of the account; they are delivered through `shim_pushers_loaded`.
+    pub(crate) fn pushers(&self) -> String {
+        let client = self.client.clone();
+        self.tasks.spawn(&self.rt, move |token| async move {
+            let response = client.send(get_pushers::v3::Request::new()).await?;
+            ffi::shim_pushers_loaded(token, response.pushers.into_iter().map(Pusher).collect());
+            Ok::<(), HttpError>(())
+        })
+    }
+
+    pub(crate) fn remove_pusher(&self, app_id: String, push_key: String) -> String {
+        let client = self.client.clone();
+        task::spawn(&self.rt, async move {
+            client.pusher().delete(pushers::ids(app_id, push_key)).await
+        })
+    }
+
+    /// Turn a push received from the push gateway into a notification, without syncing.
+    ///
+    /// The notification is delivered through `shim_push_resolved`; if the push rules say not to notify for the event,
+    /// the task finishes without it.
+    pub(crate) fn resolve_push(&self, payload: String) -> String {
+        let client = self.client.clone();
+        self.tasks.spawn(&self.rt, move |token| async move {
+            let (room_id, event_id) = pushers::event(&payload)?;
+            let notification_client =
+                NotificationClient::new(client, NotificationProcessSetup::MultipleProcesses)
+                    .await
+                    .map_err(|error| error.to_string())?;
+            if let Some(notification) =
+                notifications::notification(&notification_client, room_id, event_id).await?
+            {
+                ffi::shim_push_resolved(token, Box::new(notification));
+            }
+            Ok::<(), String>(())
+        })
+    }
+
+    /// Browse the rooms in a space, including the ones the local user hasn't joined.
+    pub(crate) fn space_hierarchy(&self, space_id: String) -> Result<Box<SpaceHierarchy>, String> {
+        let space_id = RoomId::parse(space_id).map_err(|error| error.to_string())?;
+        Ok(SpaceHierarchy::new(space_id))
+    }
+
+    /// Whether the send queues of all rooms are enabled.
+    pub(crate) fn is_send_queue_enabled(&self) -> bool {
+        self.client.send_queue().is_enabled()
+    }
+
+    /// The events in the send queues of all rooms that haven't been sent yet.
+    pub(crate) fn pending_events(&self) -> Vec<PendingEvent> {
+        let client = self.client.clone();
+        self.rt.block_on(async move {
+            let mut events = Vec::new();
+            for room in client.joined_rooms() {
+                events.extend(sendqueue::pending_events(&room).await);
+            }
+            events
+        })
+    }
+
+    /// Enable or disable the send queues of all rooms.
+    pub(crate) fn set_send_queue_enabled(&self, enabled: bool) {
+        let client = self.client.clone();
+        self.rt.spawn(async move {
+            client.send_queue().set_enabled(enabled).await;
+        });
+    }
+
+    /// Join a room by its id or alias, trying to join through the servers in `via`.
+    pub(crate) fn join_room(&self, id_or_alias: String, via: Vec<String>) -> String {
+        let client = self.client.clone();
+        task::spawn(&self.rt, async move {
+            let id_or_alias = RoomOrAliasId::parse(id_or_alias).map_err(|error| error.to_string())?;
+            let via = server_names(via)?;
+            client
+                .join_room_by_id_or_alias(&id_or_alias, &via)
+                .await
+                .map_err(|error| error.to_string())
+        })
+    }
+
+    /// Ask to join a room with the `knock` join rule.
+    pub(crate) fn knock(&self, id_or_alias: String, reason: String, via: Vec<String>) -> String {
+        let client = self.client.clone();
+        task::spawn(&self.rt, async move {
+            let id_or_alias = RoomOrAliasId::parse(id_or_alias).map_err(|error| error.to_string())?;
+            let via = server_names(via)?;
+            let reason = (!reason.is_empty()).then_some(reason);
+            client
+                .knock(id_or_alias, reason, via)
+                .await
+                .map_err(|error| error.to_string())
+        })
+    }
+
+    /// The id of the direct chat with `user_id` according to the `m.direct` account data, or an empty string.
+    pub(crate) fn dm_room_for(&self, user_id: String) -> String {
+        let Ok(user_id) = UserId::parse(user_id) else {
+            return Default::default();
+        };
+        self.client
+            .get_dm_room(&user_id)
+            .map(|room| room.room_id().to_string())
+            .unwrap_or_default()
+    }
+
+    /// Create a direct chat with `user_id` and add it to the `m.direct` account data.
+    pub(crate) fn create_dm(&self, user_id: String, encrypted: bool) -> String {
+        let mut options = room_create_options_new();
+        options.set_invite(vec![user_id]);
+        options.set_is_direct(true);
+        options.set_preset(2);
+        options.set_encrypted(encrypted);
+        // Creating a room with is_direct set also marks it as direct chat with the invited users
+        self.create_room(&options)
+    }
+
     pub(crate) fn is_known_room(&self, id: String) -> bool {
         let room_id = RoomId::parse(id).unwrap();
         self.client.get_room(&room_id).is_some()
@@ -18,6 +18,21 @@ pub mod ffi {
         type RoomListVecDiff;
         type RoomCreateOptions;
         type Room;
+        type Reaction;
+        type ReactionSender;
+        type ThreadSummary;
+        type PendingEvent;
+        type AttachmentOptions;
+        type ReadReceipt;
+        type Subscription;
+        type Notification;
+        type Pusher;
+        type SpaceHierarchy;
+        type SpaceHierarchyRoom;
+        type Member;
+        type Members;
+        type MemberListVecDiff;
+        type Tag;
 
         pub fn body(self: &RoomTombstoneEventContent) -> String;
         pub fn replacement_room(self: &RoomTombstoneEventContent) -> String;
@@ -28,25 +43,111 @@ pub mod ffi {
         fn device_id(self: &Connection) -> String;
         fn matrix_id(self: &Connection) -> String;
         fn slide(self: &Connection) -> Box<Rooms>;
-        fn room_avatar(self: &Connection, room_id: String);
+        fn room_avatar(self: &Connection, room_id: String, width: u32, height: u32) -> String;
+        fn user_avatar(self: &Connection, user_id: String, room_id: String, width: u32, height: u32) -> String;
         fn timeline(self: &Connection, room_id: String) -> Box<Timeline>;
+        fn thread_timeline(self: &Connection, room_id: String, root_event_id: String) -> Result<Box<Timeline>>;
+        fn event_timeline(self: &Connection, room_id: String, event_id: String, num_context_events: u16) -> Result<Box<Timeline>>;
         fn session(self: &Connection) -> String;
-        fn timeline_paginate_back(self: &Connection, timeline: &Timeline);
+        fn timeline_paginate_back(self: &Connection, timeline: &Timeline, num_events: u16) -> String;
+        fn timeline_paginate_forward(self: &Connection, timeline: &Timeline, num_events: u16) -> String;
         fn logout(self: &Connection);
-        fn create_room(self: &Connection, room_create_options: &RoomCreateOptions);
+        fn create_room(self: &Connection, room_create_options: &RoomCreateOptions) -> String;
         fn room(self: &Connection, id: String) -> Box<Room>;
         fn is_known_room(self: &Connection, id: String) -> bool;
+        fn join_room(self: &Connection, id_or_alias: String, via: Vec<String>) -> String;
+        fn knock(self: &Connection, id_or_alias: String, reason: String, via: Vec<String>) -> String;
+        fn is_send_queue_enabled(self: &Connection) -> bool;
+        fn set_send_queue_enabled(self: &Connection, enabled: bool);
+        fn pending_events(self: &Connection) -> Vec<PendingEvent>;
+        fn cancel_task(self: &Connection, token: String);
+        fn media_content(self: &Connection, source: String, with_data: bool) -> String;
+        fn media_thumbnail(self: &Connection, source: String, width: u32, height: u32, method: u8, with_data: bool) -> String;
+        fn set_media_cache_size(self: &Connection, max_size: u64);
+        fn clear_media_cache(self: &Connection);
 
         fn set_display_name(self: &Connection, display_name: String) -> String;
+        fn set_date_divider_mode(self: &Connection, mode: u8);
+        fn default_notification_mode(self: &Connection, encrypted: bool, one_to_one: bool) -> u8;
+        fn set_default_notification_mode(self: &Connection, encrypted: bool, one_to_one: bool, mode: u8) -> String;
+        fn can_push_encrypted_events(self: &Connection) -> bool;
+        fn is_push_rule_enabled(self: &Connection, rule: u8) -> bool;
+        fn set_push_rule_enabled(self: &Connection, rule: u8, enabled: bool) -> String;
+        fn keywords(self: &Connection) -> Vec<String>;
+        fn add_keyword(self: &Connection, keyword: String) -> String;
+        fn remove_keyword(self: &Connection, keyword: String) -> String;
+        fn subscribe_to_push_rules(self: &Connection) -> Box<Subscription>;
+        fn register_pusher(
+            self: &Connection,
+            app_id: String,
+            push_key: String,
+            url: String,
+            format: String,
+            app_display_name: String,
+            device_display_name: String,
+            lang: String,
+        ) -> String;
+        fn pushers(self: &Connection) -> String;
+        fn remove_pusher(self: &Connection, app_id: String, push_key: String) -> String;
+        fn resolve_push(self: &Connection, payload: String) -> String;
+        fn space_hierarchy(self: &Connection, space_id: String) -> Result<Box<SpaceHierarchy>>;
+        fn dm_room_for(self: &Connection, user_id: String) -> String;
+        fn create_dm(self: &Connection, user_id: String, encrypted: bool) -> String;
 
         fn id(self: &TimelineItem) -> String;
         fn body(self: &TimelineItem) -> String;
         fn box_me(self: &TimelineItem) -> Box<TimelineItem>;
-        fn timestamp(self: &TimelineItem) -> String;
+        fn kind(self: &TimelineItem) -> u8;
+        fn timestamp(self: &TimelineItem) -> u64;
+        fn is_redacted(self: &TimelineItem) -> bool;
+        fn redacted_by(self: &TimelineItem) -> String;
+        fn redaction_reason(self: &TimelineItem) -> String;
+        fn reactions(self: &TimelineItem, connection: &Connection) -> Vec<Reaction>;
+        fn read_receipts(self: &TimelineItem) -> Vec<ReadReceipt>;
+        fn transaction_id(self: &TimelineItem) -> String;
+        fn send_state(self: &TimelineItem) -> u8;
+        fn send_error(self: &TimelineItem) -> String;
+        fn media_source(self: &TimelineItem) -> String;
+        fn thread_root(self: &TimelineItem) -> String;
+        fn thread_summary(self: &TimelineItem) -> Box<ThreadSummary>;
 
         fn queue_next(self: &Timeline) -> Box<VecDiff>;
         fn has_queued_item(self: &Timeline) -> bool;
-        fn send_message(self: &Timeline, connection: &Connection, message: String);
+        fn back_pagination_status(self: &Timeline) -> u8;
+        fn id(self: &Timeline) -> String;
+        fn send_message(self: &Timeline, connection: &Connection, message: String) -> String;
+        fn mark_as_read(self: &Timeline, connection: &Connection, receipt_type: u8) -> String;
+        fn retry_send(self: &Timeline, connection: &Connection, item: &TimelineItem) -> String;
+        fn cancel_send(self: &Timeline, connection: &Connection, item: &TimelineItem) -> String;
+        fn send_attachment(self: &Timeline, connection: &Connection, path: String, options: &AttachmentOptions) -> String;
+
+        fn attachment_options_new() -> Box<AttachmentOptions>;
+        fn set_caption(self: &mut AttachmentOptions, caption: String);
+        fn set_dimensions(self: &mut AttachmentOptions, width: u32, height: u32);
+        fn set_duration(self: &mut AttachmentOptions, duration_ms: u64);
+        fn set_thumbnail(self: &mut AttachmentOptions, path: String, width: u32, height: u32);
+        fn set_voice_message(self: &mut AttachmentOptions, voice_message: bool);
+        fn redact(self: &Timeline, connection: &Connection, item: &TimelineItem, reason: String) -> String;
+        fn toggle_reaction(self: &Timeline, connection: &Connection, item: &TimelineItem, key: String) -> String;
+
+        fn key(self: &Reaction) -> String;
+        fn count(self: &Reaction) -> usize;
+        fn reacted_by_me(self: &Reaction) -> bool;
+        fn send_state(self: &Reaction) -> u8;
+        fn senders(self: &Reaction) -> Vec<ReactionSender>;
+
+        fn user_id(self: &ReactionSender) -> String;
+        fn timestamp(self: &ReactionSender) -> u64;
+        fn is_sent(self: &ReactionSender) -> bool;
+
+        fn user_id(self: &ReadReceipt) -> String;
+        fn timestamp(self: &ReadReceipt) -> u64;
+
+        fn is_thread_root(self: &ThreadSummary) -> bool;
+        fn num_replies(self: &ThreadSummary) -> u32;
+        fn participated(self: &ThreadSummary) -> bool;
+        fn latest_sender(self: &ThreadSummary) -> String;
+        fn latest_body(self: &ThreadSummary) -> String;
 
         fn queue_next(self: &Rooms) -> Box<RoomListVecDiff>;
         fn has_queued_item(self: &Rooms) -> bool;
@@ -67,6 +168,15 @@ pub mod ffi {
         fn set_room_alias(self: &mut RoomCreateOptions, alias: String);
         fn set_topic(self: &mut RoomCreateOptions, topic: String);
         fn set_visibility_public(self: &mut RoomCreateOptions, visibility_public: bool);
+        fn set_space(self: &mut RoomCreateOptions, space: bool);
+        fn set_preset(self: &mut RoomCreateOptions, preset: u8);
+        fn set_is_direct(self: &mut RoomCreateOptions, is_direct: bool);
+        fn set_room_version(self: &mut RoomCreateOptions, room_version: String);
+        fn set_encrypted(self: &mut RoomCreateOptions, encrypted: bool);
+        fn set_history_visibility(self: &mut RoomCreateOptions, history_visibility: u8);
+        fn set_join_rule(self: &mut RoomCreateOptions, join_rule: u8);
+        fn set_guest_access(self: &mut RoomCreateOptions, guest_access: bool);
+        fn set_power_level_override(self: &mut RoomCreateOptions, power_levels: String);
 
         fn id(self: &Room) -> String;
         fn state(self: &Room) -> u8;
@@ -80,6 +190,85 @@ pub mod ffi {
         fn num_unread_mentions(self: &Room) -> u64;
         fn is_favourite(self: &Room) -> bool;
         fn is_low_priority(self: &Room) -> bool;
+        fn redact(self: &Room, connection: &Connection, event_id: String, reason: String) -> String;
+        fn is_send_queue_enabled(self: &Room) -> bool;
+        fn set_send_queue_enabled(self: &Room, enabled: bool);
+        fn pending_events(self: &Room, connection: &Connection) -> Vec<PendingEvent>;
+        fn send_receipt(self: &Room, connection: &Connection, event_id: String, receipt_type: u8) -> String;
+        fn is_marked_unread(self: &Room) -> bool;
+        fn set_marked_unread(self: &Room, connection: &Connection, unread: bool) -> String;
+        fn typing_notice(self: &Room, connection: &Connection, typing: bool) -> String;
+        fn subscribe_to_typing(self: &Room, connection: &Connection) -> Box<Subscription>;
+        fn accept_invite(self: &Room, connection: &Connection) -> String;
+        fn decline_invite(self: &Room, connection: &Connection) -> String;
+        fn leave(self: &Room, connection: &Connection) -> String;
+        fn forget(self: &Room, connection: &Connection) -> String;
+        fn invite_user(self: &Room, connection: &Connection, user_id: String) -> String;
+        fn kick(self: &Room, connection: &Connection, user_id: String, reason: String) -> String;
+        fn ban(self: &Room, connection: &Connection, user_id: String, reason: String) -> String;
+        fn unban(self: &Room, connection: &Connection, user_id: String, reason: String) -> String;
+        fn can_invite(self: &Room, connection: &Connection) -> bool;
+        fn can_kick(self: &Room, connection: &Connection) -> bool;
+        fn can_ban(self: &Room, connection: &Connection) -> bool;
+        fn can_redact_others(self: &Room, connection: &Connection) -> bool;
+        fn members(self: &Room, connection: &Connection) -> Box<Members>;
+        fn search_members(self: &Room, connection: &Connection, query: String) -> Vec<Member>;
+        fn set_name(self: &Room, connection: &Connection, name: String) -> String;
+        fn set_topic(self: &Room, connection: &Connection, topic: String) -> String;
+        fn set_avatar(self: &Room, connection: &Connection, path: String) -> String;
+        fn canonical_alias(self: &Room) -> String;
+        fn alt_aliases(self: &Room) -> Vec<String>;
+        fn set_canonical_alias(self: &Room, connection: &Connection, alias: String, alt_aliases: Vec<String>) -> String;
+        fn local_aliases(self: &Room, connection: &Connection) -> String;
+        fn add_local_alias(self: &Room, connection: &Connection, alias: String) -> String;
+        fn remove_local_alias(self: &Room, connection: &Connection, alias: String) -> String;
+        fn set_favourite(self: &Room, connection: &Connection, favourite: bool, order: f64) -> String;
+        fn set_low_priority(self: &Room, connection: &Connection, low_priority: bool, order: f64) -> String;
+        fn set_tag(self: &Room, connection: &Connection, name: String, order: f64) -> String;
+        fn remove_tag(self: &Room, connection: &Connection, name: String) -> String;
+        fn add_child(
+            self: &Room,
+            connection: &Connection,
+            room_id: String,
+            via: Vec<String>,
+            order: String,
+            suggested: bool,
+        ) -> String;
+        fn remove_child(self: &Room, connection: &Connection, room_id: String) -> String;
+        fn set_canonical_parent(self: &Room, connection: &Connection, space_id: String, via: Vec<String>) -> String;
+        fn is_direct(self: &Room, connection: &Connection) -> bool;
+        fn direct_targets(self: &Room) -> Vec<String>;
+        fn notification_mode(self: &Room, connection: &Connection) -> u8;
+        fn has_custom_notification_mode(self: &Room) -> bool;
+        fn supports_mentions_only(self: &Room, connection: &Connection) -> bool;
+        fn set_notification_mode(self: &Room, connection: &Connection, mode: u8) -> String;
+        fn reset_notification_mode(self: &Room, connection: &Connection) -> String;
+
+        fn name(self: &Tag) -> String;
+        fn order(self: &Tag) -> f64;
+
+        fn queue_next(self: &Members) -> Box<MemberListVecDiff>;
+        fn has_queued_item(self: &Members) -> bool;
+
+        fn op(self: &MemberListVecDiff) -> u8;
+        fn index(self: &MemberListVecDiff) -> usize;
+        fn item(self: &MemberListVecDiff) -> Box<Member>;
+        fn items_vec(self: &MemberListVecDiff) -> Vec<Member>;
+
+        fn user_id(self: &Member) -> String;
+        fn display_name(self: &Member) -> String;
+        fn avatar_url(self: &Member) -> String;
+        fn membership(self: &Member) -> u8;
+        fn power_level(self: &Member) -> i64;
+        fn role(self: &Member) -> u8;
+        fn is_ignored(self: &Member) -> bool;
+        fn presence(self: &Member, connection: &Connection) -> u8;
+        fn box_me(self: &Member) -> Box<Member>;
+
+        fn room_id(self: &PendingEvent) -> String;
+        fn transaction_id(self: &PendingEvent) -> String;
+        fn event_type(self: &PendingEvent) -> String;
+        fn error(self: &PendingEvent) -> String;
 
         fn id(self: &RoomListItem) -> String;
         fn state(self: &RoomListItem) -> u8;
@@ -94,7 +283,45 @@ pub mod ffi {
         fn canonical_alias(self: &RoomListItem) -> String;
         fn is_favourite(self: &RoomListItem) -> bool;
         fn is_low_priority(self: &RoomListItem) -> bool;
+        fn is_marked_unread(self: &RoomListItem) -> bool;
+        fn tags(self: &RoomListItem, connection: &Connection) -> Vec<Tag>;
+        fn is_direct(self: &RoomListItem, connection: &Connection) -> bool;
+        fn direct_targets(self: &RoomListItem) -> Vec<String>;
+        fn notification_mode(self: &RoomListItem, connection: &Connection) -> u8;
         fn box_me(self: &RoomListItem) -> Box<RoomListItem>;
+
+        fn room_id(self: &Notification) -> String;
+        fn event_id(self: &Notification) -> String;
+        fn room_name(self: &Notification) -> String;
+        fn sender_id(self: &Notification) -> String;
+        fn sender_name(self: &Notification) -> String;
+        fn body(self: &Notification) -> String;
+        fn is_mention(self: &Notification) -> bool;
+        fn is_noisy(self: &Notification) -> bool;
+        fn is_invite(self: &Notification) -> bool;
+        fn is_direct(self: &Notification) -> bool;
+
+        fn app_id(self: &Pusher) -> String;
+        fn push_key(self: &Pusher) -> String;
+        fn app_display_name(self: &Pusher) -> String;
+        fn device_display_name(self: &Pusher) -> String;
+        fn url(self: &Pusher) -> String;
+        fn format(self: &Pusher) -> String;
+
+        fn paginate(self: &SpaceHierarchy, connection: &Connection) -> String;
+        fn at_end(self: &SpaceHierarchy) -> bool;
+
+        fn room_id(self: &SpaceHierarchyRoom) -> String;
+        fn parent_id(self: &SpaceHierarchyRoom) -> String;
+        fn name(self: &SpaceHierarchyRoom) -> String;
+        fn topic(self: &SpaceHierarchyRoom) -> String;
+        fn avatar_url(self: &SpaceHierarchyRoom) -> String;
+        fn canonical_alias(self: &SpaceHierarchyRoom) -> String;
+        fn num_joined_members(self: &SpaceHierarchyRoom) -> u64;
+        fn join_rule(self: &SpaceHierarchyRoom) -> u8;
+        fn is_space(self: &SpaceHierarchyRoom) -> bool;
+        fn is_suggested(self: &SpaceHierarchyRoom) -> bool;
+        fn membership(self: &SpaceHierarchyRoom) -> u8;
     }
 
     unsafe extern "C++" {
@@ -103,11 +330,34 @@ pub mod ffi {
         pub fn shim_connected(matrix_id: String);
         pub fn shim_rooms_changed(matrix_id: String);
         pub fn shim_timeline_changed(matrix_id: String, room_id: String);
-        pub fn shim_avatar_loaded(room_id: String, data: Vec<u8>);
+        pub fn shim_back_pagination_status_changed(
+            matrix_id: String,
+            room_id: String,
+            timeline_id: String,
+            status: u8,
+        );
+        pub fn shim_avatar_loaded(token: String, data: Vec<u8>);
+        pub fn shim_user_avatar_loaded(token: String, data: Vec<u8>);
+        pub fn shim_media_loaded(token: String, path: String, data: Vec<u8>);
         pub fn shim_logged_out(matrix_id: String);
+        pub fn shim_members_changed(matrix_id: String, room_id: String);
+        pub fn shim_push_rules_changed(matrix_id: String);
+        pub fn shim_notification_received(matrix_id: String, notification: Box<Notification>);
+        pub fn shim_push_resolved(token: String, notification: Box<Notification>);
+        pub fn shim_pushers_loaded(token: String, pushers: Vec<Pusher>);
+        pub fn shim_space_hierarchy_loaded(token: String, rooms: Vec<SpaceHierarchyRoom>);
+        pub fn shim_local_aliases_loaded(token: String, aliases: Vec<String>);
+        pub fn shim_typing_users_changed(
+            matrix_id: String,
+            room_id: String,
+            user_ids: Vec<String>,
+            display_names: Vec<String>,
+        );
 
         pub fn shim_oidc_login_url_available(server_name: String, url: String);
 
         pub fn task_done(token: String);
+        pub fn task_failed(token: String, error: String);
+        pub fn task_progress(token: String, current: u64, total: u64);
     }
 }
\ No newline at end of file
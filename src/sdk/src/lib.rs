@@ -1,37 +1,76 @@
 // SPDX-FileCopyrightText: 2025 Tobias Fella <tobias.fella@kde.org>
 // SPDX-License-Identifier: GPL-2.0-or-later
 
-use chrono::prelude::{DateTime, Utc};
 use matrix_sdk::authentication::oauth::{ClientId, UserSession};
 use matrix_sdk::{
     authentication::matrix::MatrixSession,
+    room::reply::{EnforceThread, Reply},
     ruma::{
-        api::client::{room::Visibility},
         events::{
-            room::message::{MessageType, RoomMessageEventContent, TextMessageEventContent},
+            relation::Thread,
+            room::message::{
+                MessageType, Relation, ReplyWithinThread, RoomMessageEventContent,
+                TextMessageEventContent,
+            },
             AnyMessageLikeEventContent,
         },
-        UserId,
+        OwnedEventId,
     },
 };
 use matrix_sdk_ui::{
     eyeball_im::VectorDiff,
     timeline::{
-        MsgLikeKind, TimelineItemContent, TimelineItemKind, VirtualTimelineItem,
+        DateDividerMode, EventSendState, MsgLikeContent, MsgLikeKind, TimelineBuilder, TimelineItemContent, TimelineItemKind, VirtualTimelineItem,
     },
 };
-use std::sync::{Arc, RwLock};
+use matrix_sdk::event_handler::EventHandlerDropGuard;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
 use matrix_sdk::ruma::exports::serde::{Deserialize, Serialize};
+use tokio::runtime::Handle;
+use tokio::task::AbortHandle;
+use tokio_stream::{Stream, StreamExt};
 
+use crate::attachment::{attachment_options_new, AttachmentOptions};
 use crate::connection::Connection;
+use crate::ffi::ffi;
+use crate::members::{Member, MemberListVecDiff, Members};
+use crate::notifications::Notification;
+use crate::pushers::Pusher;
+use crate::reaction::{Reaction, ReactionSender};
+use crate::receipt::ReadReceipt;
+use crate::redaction::{Redaction, Redactions};
 use crate::room::Room;
+use crate::sendqueue::PendingEvent;
+use crate::spacehierarchy::{SpaceHierarchy, SpaceHierarchyRoom};
+use crate::tag::Tag;
+use crate::task::Subscription;
+use crate::thread::ThreadSummary;
+use crate::roomcreateoptions::{room_create_options_new, RoomCreateOptions};
 use crate::roomlistitem::RoomListItem;
 use crate::tombstone::RoomTombstoneEventContent;
 
+mod attachment;
+mod media;
+mod members;
+mod notifications;
+mod notificationsettings;
+mod pushers;
+mod pushrules;
+mod reaction;
+mod redaction;
+mod receipt;
 mod room;
+mod roomcreateoptions;
+mod sendqueue;
+mod spacehierarchy;
 mod roomlistitem;
 mod tombstone;
 mod connection;
+mod tag;
+mod task;
+mod thread;
 
 mod ffi;
 
@@ -124,18 +163,72 @@ impl RoomListVecDiff {
 //     }
 // }
 
+#[derive(Clone, Copy)]
+enum BackPaginationStatus {
+    Idle = 0,
+    Paginating = 1,
+    HitStart = 2,
+}
+
 /* There's two different types of RwLock in here!
  * Timeline.0 uses std::sync::RwLock, since this isn't async, which makes it easier to call from C++
  * Timeline.1 uses tokio's RwLock, which can be used in more complex async scenarios, but can only be acquired in an async function
  */
+#[derive(Clone)]
 struct Timeline {
     queue: Arc<RwLock<Vec<VectorDiff<Arc<matrix_sdk_ui::timeline::TimelineItem>>>>>,
     timeline: Arc<tokio::sync::RwLock<matrix_sdk_ui::timeline::Timeline>>,
+    /// Root of the thread if this timeline is focused on one
+    thread_root: Option<OwnedEventId>,
+    date_divider_mode: DateDividerMode,
+    /// Whether this timeline is focused on an event and hasn't switched to the live timeline yet
+    event_focused: Arc<AtomicBool>,
+    /// Task moving the diffs of `timeline` into `queue`
+    forwarder: Arc<Forwarder>,
+    /// Token of the running back pagination task, if any
+    back_pagination: Arc<Mutex<Option<String>>>,
+    back_pagination_status: Arc<AtomicU8>,
+    /// Token of the running forward pagination task, if any
+    forward_pagination: Arc<Mutex<Option<String>>>,
+    /// Redactions received while the timeline is open
+    redactions: Redactions,
+    /// Keeps `redactions` updated for as long as the timeline exists
+    _redaction_handler: Arc<EventHandlerDropGuard>,
+    /// Identifies this timeline in callbacks, since a room can have several timelines
+    id: String,
+    matrix_id: String,
+    room_id: String,
 }
 
-struct TimelineItem(Arc<matrix_sdk_ui::timeline::TimelineItem>);
+/// The latest event of a thread timeline that was sent already, for the reply fallback of thread messages.
+async fn latest_event_id(
+    timeline: &matrix_sdk_ui::timeline::Timeline,
+    thread_root: OwnedEventId,
+) -> OwnedEventId {
+    timeline
+        .latest_event()
+        .await
+        .and_then(|event| event.event_id().map(ToOwned::to_owned))
+        .unwrap_or(thread_root)
+}
 
-struct VecDiff(VectorDiff<Arc<matrix_sdk_ui::timeline::TimelineItem>>);
+/// Handle of the task forwarding a timeline's diffs, shared by all clones of the [`Timeline`].
+///
+/// Once C++ dropped the timeline and no task uses it anymore, the forwarding stops.
+#[derive(Default)]
+struct Forwarder(Mutex<Option<AbortHandle>>);
+
+impl Drop for Forwarder {
+    fn drop(&mut self) {
+        if let Some(handle) = self.0.lock().unwrap().take() {
+            handle.abort();
+        }
+    }
+}
+
+struct TimelineItem(Arc<matrix_sdk_ui::timeline::TimelineItem>, Redactions);
+
+struct VecDiff(VectorDiff<Arc<matrix_sdk_ui::timeline::TimelineItem>>, Redactions);
 
 impl VecDiff {
     fn op(&self) -> u8 {
@@ -166,52 +259,280 @@ impl VecDiff {
 
     fn item(&self) -> Box<TimelineItem> {
         match &self.0 {
-            VectorDiff::Insert { value, .. } => Box::new(TimelineItem(value.clone())),
-            VectorDiff::Set { value, .. } => Box::new(TimelineItem(value.clone())),
-            VectorDiff::PushFront { value, .. } => Box::new(TimelineItem(value.clone())),
-            VectorDiff::PushBack { value, .. } => Box::new(TimelineItem(value.clone())),
+            VectorDiff::Insert { value, .. } => {
+                Box::new(TimelineItem(value.clone(), self.1.clone()))
+            }
+            VectorDiff::Set { value, .. } => Box::new(TimelineItem(value.clone(), self.1.clone())),
+            VectorDiff::PushFront { value, .. } => {
+                Box::new(TimelineItem(value.clone(), self.1.clone()))
+            }
+            VectorDiff::PushBack { value, .. } => {
+                Box::new(TimelineItem(value.clone(), self.1.clone()))
+            }
             _ => panic!(),
         }
     }
 
     fn items_vec(&self) -> Vec<TimelineItem> {
         match &self.0 {
-            VectorDiff::Append { values, .. } => {
-                values.iter().map(|ti| TimelineItem(ti.clone())).collect()
-            }
-            VectorDiff::Reset { values, .. } => {
-                values.iter().map(|ti| TimelineItem(ti.clone())).collect()
-            }
+            VectorDiff::Append { values, .. } => values
+                .iter()
+                .map(|ti| TimelineItem(ti.clone(), self.1.clone()))
+                .collect(),
+            VectorDiff::Reset { values, .. } => values
+                .iter()
+                .map(|ti| TimelineItem(ti.clone(), self.1.clone()))
+                .collect(),
             _ => panic!(),
         }
     }
 }
 
 impl Timeline {
+    /// Push `initial` to the queue, followed by all diffs from `stream`.
+    ///
+    /// Stops forwarding the diffs of a previous stream.
+    pub(crate) fn forward(
+        &self,
+        rt: &Handle,
+        initial: VectorDiff<Arc<matrix_sdk_ui::timeline::TimelineItem>>,
+        stream: impl Stream<Item = Vec<VectorDiff<Arc<matrix_sdk_ui::timeline::TimelineItem>>>>
+            + Send
+            + 'static,
+    ) {
+        let mut forwarder = self.forwarder.0.lock().unwrap();
+        if let Some(previous) = forwarder.take() {
+            previous.abort();
+        }
+
+        let queue = self.queue.clone();
+        let matrix_id = self.matrix_id.clone();
+        let room_id = self.room_id.clone();
+        let handle = rt.spawn(async move {
+            tokio::pin!(stream);
+
+            queue.write().unwrap().push(initial);
+            ffi::shim_timeline_changed(matrix_id.clone(), room_id.clone());
+
+            while let Some(entries) = stream.next().await {
+                for entry in entries {
+                    queue.write().unwrap().push(entry);
+                }
+                ffi::shim_timeline_changed(matrix_id.clone(), room_id.clone());
+            }
+        });
+        *forwarder = Some(handle.abort_handle());
+    }
+
+    /// Replace an event-focused timeline with the live timeline of the same room.
+    pub(crate) async fn switch_to_live(&self, rt: &Handle) -> Result<(), matrix_sdk_ui::timeline::Error> {
+        let mut timeline = self.timeline.write().await;
+        let live = TimelineBuilder::new(timeline.room())
+            .with_date_divider_mode(self.date_divider_mode.clone())
+            .build()
+            .await?;
+        let (items, stream) = live.subscribe().await;
+        *timeline = live;
+        self.event_focused.store(false, Ordering::Relaxed);
+        self.forward(rt, VectorDiff::Reset { values: items }, stream);
+        Ok(())
+    }
+
+    pub(crate) fn set_back_pagination_status(&self, status: BackPaginationStatus) {
+        self.back_pagination_status
+            .store(status as u8, Ordering::Relaxed);
+        ffi::shim_back_pagination_status_changed(
+            self.matrix_id.clone(),
+            self.room_id.clone(),
+            self.id.clone(),
+            status as u8,
+        );
+    }
+
+    fn id(&self) -> String {
+        self.id.clone()
+    }
+
+    /// 0: Idle, 1: Paginating, 2: Reached the start of the timeline
+    fn back_pagination_status(&self) -> u8 {
+        self.back_pagination_status.load(Ordering::Relaxed)
+    }
+
     fn has_queued_item(&self) -> bool {
         !self.queue.read().unwrap().is_empty()
     }
 
     fn queue_next(&self) -> Box<VecDiff> {
         let mut write = self.queue.write().unwrap();
-        let item = Box::new(VecDiff(write.first().unwrap().clone()));
+        let item = Box::new(VecDiff(write.first().unwrap().clone(), self.redactions.clone()));
         write.remove(0);
         item
     }
 
-    fn send_message(&self, connection: &Connection, message: String) {
+    /// Queue a text message for sending.
+    ///
+    /// The task finishes once the message is in the send queue; whether it was sent is reflected by the local echo's
+    /// send state.
+    fn send_message(&self, connection: &Connection, message: String) -> String {
         let timeline = self.timeline.clone();
-        connection.rt.spawn(async move {
-            let content = RoomMessageEventContent::new(MessageType::Text(
+        let thread_root = self.thread_root.clone();
+        task::spawn(&connection.rt, async move {
+            let mut content = RoomMessageEventContent::new(MessageType::Text(
                 TextMessageEventContent::plain(message),
             ));
+            let timeline = timeline.write().await;
+            if let Some(thread_root) = thread_root {
+                let latest = latest_event_id(&timeline, thread_root.clone()).await;
+                content.relates_to = Some(Relation::Thread(Thread::plain(thread_root, latest)));
+            }
             timeline
-                .write()
-                .await
                 .send(AnyMessageLikeEventContent::RoomMessage(content))
                 .await
-                .unwrap();
-        });
+        })
+    }
+
+    /// Send the file at `path` through the send queue, encrypted if the room is encrypted.
+    ///
+    /// The file shows up as local echo right away; in a thread timeline, it is sent into the thread. The task reports
+    /// the upload progress and finishes once the event is sent. Cancelling the task removes the local echo, like
+    /// [`Timeline::cancel_send`].
+    fn send_attachment(
+        &self,
+        connection: &Connection,
+        path: String,
+        options: &AttachmentOptions,
+    ) -> String {
+        let timeline = self.timeline.clone();
+        let thread_root = self.thread_root.clone();
+        let options = options.clone();
+        connection.tasks.spawn(&connection.rt, move |token| async move {
+            let path = PathBuf::from(path);
+            let data = tokio::fs::read(&path)
+                .await
+                .map_err(|error| error.to_string())?;
+            let content_type = mime_guess::from_path(&path).first_or_octet_stream();
+            let filename = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let mut config = options
+                .config(&content_type, data.len())
+                .await
+                .map_err(|error| error.to_string())?;
+            if let Some(thread_root) = thread_root {
+                // Like text messages, reply to the latest event in the thread as fallback for clients without threads
+                let latest = latest_event_id(&*timeline.read().await, thread_root).await;
+                config = config.reply(Some(Reply {
+                    event_id: latest,
+                    enforce_thread: EnforceThread::Threaded(ReplyWithinThread::No),
+                }));
+            }
+
+            let room = timeline.read().await.room().clone();
+            let queue = room.send_queue();
+            let (_, updates) = queue.subscribe().await.map_err(|error| error.to_string())?;
+            room.client().send_queue().enable_upload_progress(true);
+            let handle = queue
+                .send_attachment(filename, content_type, data, config)
+                .await
+                .map_err(|error| error.to_string())?;
+            sendqueue::wait_until_sent(handle, updates, token).await
+        })
+    }
+
+    /// Send a receipt for the latest event in the timeline.
+    ///
+    /// `receipt_type` is 0 for a public read receipt, 1 for a private one and 2 for the fully read marker.
+    fn mark_as_read(&self, connection: &Connection, receipt_type: u8) -> String {
+        let timeline = self.timeline.clone();
+        task::spawn(&connection.rt, async move {
+            timeline
+                .read()
+                .await
+                .mark_as_read(receipt::receipt_type(receipt_type))
+                .await
+        })
+    }
+
+    /// Try sending a local echo again after it failed.
+    fn retry_send(&self, connection: &Connection, item: &TimelineItem) -> String {
+        let timeline = self.timeline.clone();
+        let transaction_id = item
+            .0
+            .as_event()
+            .and_then(|event| event.transaction_id().map(|id| id.to_owned()));
+        task::spawn(&connection.rt, async move {
+            let Some(transaction_id) = transaction_id else {
+                return Err("Only local echoes can be retried".to_string());
+            };
+            let room = timeline.read().await.room().clone();
+            let Some(handle) = sendqueue::send_handle(&room, &transaction_id).await else {
+                return Err("The event is not in the send queue anymore".to_string());
+            };
+            handle.unwedge().await.map_err(|error| error.to_string())?;
+            room.send_queue().set_enabled(true);
+            Ok(())
+        })
+    }
+
+    /// Remove a local echo from the send queue before it is sent.
+    fn cancel_send(&self, connection: &Connection, item: &TimelineItem) -> String {
+        let timeline = self.timeline.clone();
+        let transaction_id = item
+            .0
+            .as_event()
+            .and_then(|event| event.transaction_id().map(|id| id.to_owned()));
+        task::spawn(&connection.rt, async move {
+            let Some(transaction_id) = transaction_id else {
+                return Err("Only local echoes can be cancelled".to_string());
+            };
+            let room = timeline.read().await.room().clone();
+            let Some(handle) = sendqueue::send_handle(&room, &transaction_id).await else {
+                return Err("The event is not in the send queue anymore".to_string());
+            };
+            match handle.abort().await {
+                Ok(true) => Ok(()),
+                Ok(false) => Err("The event has already been sent".to_string()),
+                Err(error) => Err(error.to_string()),
+            }
+        })
+    }
+
+    /// Redact the given item with an optional reason.
+    ///
+    /// If the item is a local echo that hasn't been sent yet, it is discarded instead.
+    fn redact(&self, connection: &Connection, item: &TimelineItem, reason: String) -> String {
+        let timeline = self.timeline.clone();
+        let identifier = item.0.as_event().map(|event| event.identifier());
+        task::spawn(&connection.rt, async move {
+            let Some(identifier) = identifier else {
+                return Err("Only events can be redacted".to_string());
+            };
+            let reason = (!reason.is_empty()).then_some(reason);
+            timeline
+                .read()
+                .await
+                .redact(&identifier, reason.as_deref())
+                .await
+                .map_err(|error| error.to_string())
+        })
+    }
+
+    /// Add the reaction `key` to the given item, or remove it if the local user already reacted with it.
+    fn toggle_reaction(&self, connection: &Connection, item: &TimelineItem, key: String) -> String {
+        let timeline = self.timeline.clone();
+        let identifier = item.0.as_event().map(|event| event.identifier());
+        task::spawn(&connection.rt, async move {
+            let Some(identifier) = identifier else {
+                return Err("Only events can be reacted to".to_string());
+            };
+            timeline
+                .read()
+                .await
+                .toggle_reaction(&identifier, &key)
+                .await
+                .map_err(|error| error.to_string())
+        })
     }
 }
 
@@ -228,83 +549,232 @@ impl TimelineItem {
             .unwrap_or_default()
     }
 
+    /// Kind of the item.
+    ///
+    /// 0: Event, 1: Date divider, 2: Read marker, 3: Start of the timeline
+    fn kind(&self) -> u8 {
+        match self.0.kind() {
+            TimelineItemKind::Event(_) => 0,
+            TimelineItemKind::Virtual(VirtualTimelineItem::DateDivider(_)) => 1,
+            TimelineItemKind::Virtual(VirtualTimelineItem::ReadMarker) => 2,
+            TimelineItemKind::Virtual(VirtualTimelineItem::TimelineStart) => 3,
+        }
+    }
+
+    /// Body of an event; empty for virtual items.
     fn body(&self) -> String {
         match self.0.kind() {
-            TimelineItemKind::Event(event) => match event.content() {
-                TimelineItemContent::MsgLike(message) => match &message.kind {
-                    MsgLikeKind::Message(message) => message.body().to_string(),
-                    MsgLikeKind::Sticker(sticker) => sticker.content().body.clone(),
-                    MsgLikeKind::Poll(_) => "poll".to_string(),
-                    MsgLikeKind::Redacted => "redacted".to_string(),
-                    MsgLikeKind::UnableToDecrypt(_) => "utd".to_string(),
-                    MsgLikeKind::Other(other) => format!("{:?}", other),
-                },
-                event => format!("{:?}", event),
-            },
-            TimelineItemKind::Virtual(virt) => match virt {
-                VirtualTimelineItem::DateDivider(millis) => format!("{}", millis.0),
-                VirtualTimelineItem::ReadMarker => "Readmarker".to_string(),
-                VirtualTimelineItem::TimelineStart => "Timeline start".to_string(),
-            },
+            TimelineItemKind::Event(event) => content_body(event.content()),
+            TimelineItemKind::Virtual(_) => Default::default(),
         }
     }
 
-    fn timestamp(&self) -> String {
+    /// Timestamp of an event or date divider, in milliseconds since the unix epoch.
+    ///
+    /// Read markers and the start of the timeline have no timestamp and return 0, which isn't a valid date.
+    fn timestamp(&self) -> u64 {
         match self.0.kind() {
-            TimelineItemKind::Event(event) => {
-                let dt: DateTime<Utc> = event.timestamp().to_system_time().unwrap().clone().into();
-                format!("{}", dt.format("%+"))
-            }
-            _ => Default::default(),
+            TimelineItemKind::Event(event) => event.timestamp().0.into(),
+            TimelineItemKind::Virtual(VirtualTimelineItem::DateDivider(millis)) => millis.0.into(),
+            _ => 0,
         }
     }
 
     fn box_me(&self) -> Box<TimelineItem> {
-        Box::new(TimelineItem(self.0.clone()))
+        Box::new(TimelineItem(self.0.clone(), self.1.clone()))
     }
-}
 
+    /// Whether this item is an event that has been redacted.
+    fn is_redacted(&self) -> bool {
+        self.0
+            .as_event()
+            .map(|event| {
+                matches!(
+                    event.content(),
+                    TimelineItemContent::MsgLike(MsgLikeContent {
+                        kind: MsgLikeKind::Redacted,
+                        ..
+                    })
+                )
+            })
+            .unwrap_or_default()
+    }
 
+    /// The user that redacted this event, or an empty string if it isn't redacted.
+    fn redacted_by(&self) -> String {
+        if let Some(redaction) = self.live_redaction() {
+            return redaction.sender;
+        }
+        self.redacted_because()
+            .and_then(|because| because.get("sender")?.as_str().map(str::to_string))
+            .unwrap_or_default()
+    }
 
-#[derive(Clone)]
-struct RoomCreateOptions(matrix_sdk::ruma::api::client::room::create_room::v3::Request);
+    /// The reason given when redacting this event, if any.
+    fn redaction_reason(&self) -> String {
+        if let Some(redaction) = self.live_redaction() {
+            return redaction.reason;
+        }
+        self.redacted_because()
+            .and_then(|because| because.get("content")?.get("reason")?.as_str().map(str::to_string))
+            .unwrap_or_default()
+    }
 
-impl RoomCreateOptions {
-    fn set_invite(&mut self, users: Vec<String>) {
-        self.0.invite = users.iter().map(|it| UserId::parse(&it).unwrap()).collect()
+    /// The redaction of this event if it happened while the timeline was open.
+    ///
+    /// Events that were already redacted when they were loaded have `redacted_because` in their JSON instead.
+    fn live_redaction(&self) -> Option<Redaction> {
+        let event_id = self.0.as_event()?.event_id()?;
+        self.1.get(event_id)
     }
 
-    fn set_name(&mut self, name: String) {
-        self.0.name = Some(name);
+    /// Read receipts of other users that have read up to this event.
+    fn read_receipts(&self) -> Vec<ReadReceipt> {
+        self.0
+            .as_event()
+            .map(|event| {
+                event
+                    .read_receipts()
+                    .iter()
+                    .map(|(user_id, receipt)| ReadReceipt {
+                        user_id: user_id.to_string(),
+                        timestamp: receipt.ts.map(|ts| ts.0.into()).unwrap_or_default(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// The reactions to this event, grouped by key.
+    fn reactions(&self, connection: &Connection) -> Vec<Reaction> {
+        let own_user_id = connection.matrix_id();
+        match self.0.as_event().map(|event| event.content()) {
+            Some(TimelineItemContent::MsgLike(message)) => message
+                .reactions
+                .iter()
+                .map(|(key, senders)| Reaction::new(key, senders.iter(), &own_user_id))
+                .collect(),
+            _ => Default::default(),
+        }
+    }
+
+    /// The transaction id of a local echo, or an empty string for remote events.
+    fn transaction_id(&self) -> String {
+        self.0
+            .as_event()
+            .and_then(|event| event.transaction_id().map(|id| id.to_string()))
+            .unwrap_or_default()
+    }
+
+    /// Send state of a local echo.
+    ///
+    /// 0: Remote event, 1: Not sent yet, 2: Sending (uploading media), 3: Sent, 4: Sending failed with a recoverable
+    /// error, 5: Sending failed with an unrecoverable error
+    fn send_state(&self) -> u8 {
+        match self.0.as_event().and_then(|event| event.send_state()) {
+            None => 0,
+            Some(EventSendState::NotSentYet { progress: None }) => 1,
+            Some(EventSendState::NotSentYet { progress: Some(_) }) => 2,
+            Some(EventSendState::Sent { .. }) => 3,
+            Some(EventSendState::SendingFailed {
+                is_recoverable: true,
+                ..
+            }) => 4,
+            Some(EventSendState::SendingFailed {
+                is_recoverable: false,
+                ..
+            }) => 5,
+        }
+    }
+
+    /// The error that stopped this local echo from being sent, or an empty string.
+    fn send_error(&self) -> String {
+        match self.0.as_event().and_then(|event| event.send_state()) {
+            Some(EventSendState::SendingFailed { error, .. }) => error.to_string(),
+            _ => Default::default(),
+        }
     }
 
-    fn set_room_alias(&mut self, alias: String) {
-        self.0.room_alias_name = Some(alias);
+    /// JSON of the media source of an image, video, audio or file message, or an empty string.
+    fn media_source(&self) -> String {
+        let source = match self.0.as_event().map(|event| event.content()) {
+            Some(TimelineItemContent::MsgLike(MsgLikeContent {
+                kind: MsgLikeKind::Message(message),
+                ..
+            })) => match message.msgtype() {
+                MessageType::Image(content) => Some(&content.source),
+                MessageType::Video(content) => Some(&content.source),
+                MessageType::Audio(content) => Some(&content.source),
+                MessageType::File(content) => Some(&content.source),
+                _ => None,
+            },
+            _ => None,
+        };
+        source
+            .and_then(|source| serde_json::to_string(source).ok())
+            .unwrap_or_default()
     }
 
-    fn set_topic(&mut self, topic: String) {
-        self.0.topic = Some(topic);
+    /// The id of the thread root if this event is part of a thread.
+    fn thread_root(&self) -> String {
+        match self.0.as_event().map(|event| event.content()) {
+            Some(TimelineItemContent::MsgLike(message)) => message
+                .thread_root
+                .as_ref()
+                .map(|id| id.to_string())
+                .unwrap_or_default(),
+            _ => Default::default(),
+        }
     }
 
-    fn set_visibility_public(&mut self, visibility_public: bool) {
-        self.0.visibility = if visibility_public {
-            Visibility::Public
-        } else {
-            Visibility::Private
+    /// Summary of the thread started by this event, if there is one.
+    fn thread_summary(&self) -> Box<ThreadSummary> {
+        let summary = match self.0.as_event().map(|event| event.content()) {
+            Some(TimelineItemContent::MsgLike(message)) => message.thread_summary.clone(),
+            _ => None,
         };
+        // The server's bundled aggregation, which isn't updated for live replies
+        let participated = self
+            .unsigned()
+            .and_then(|unsigned| {
+                unsigned
+                    .get("m.relations")?
+                    .get("m.thread")?
+                    .get("current_user_participated")?
+                    .as_bool()
+            })
+            .unwrap_or_default();
+        Box::new(ThreadSummary::new(summary, participated))
+    }
+
+    /// The `unsigned.redacted_because` field of the event's JSON, which the server adds to redacted events.
+    fn redacted_because(&self) -> Option<serde_json::Value> {
+        self.unsigned()?.get("redacted_because").cloned()
+    }
+
+    /// The `unsigned` field of the event's JSON.
+    fn unsigned(&self) -> Option<serde_json::Value> {
+        self.0
+            .as_event()?
+            .latest_json()?
+            .get_field::<serde_json::Value>("unsigned")
+            .ok()?
     }
-    // creation_content: Option<Raw<CreationContent>>,
-    // initial_state: Vec<Raw<AnyInitialStateEvent>>,
-    // power_level_content_override: Option<Raw<RoomPowerLevelsEventContent>>,
-    // preset: Option<RoomPreset>,
-    // room_alias_name: Option<String>,
-    // room_version: Option<RoomVersionId>,
 }
 
-fn room_create_options_new() -> Box<RoomCreateOptions> {
-    Box::new(RoomCreateOptions(
-        matrix_sdk::ruma::api::client::room::create_room::v3::Request::new(),
-    ))
+/// Short textual representation of an event's content.
+pub(crate) fn content_body(content: &TimelineItemContent) -> String {
+    match content {
+        TimelineItemContent::MsgLike(message) => match &message.kind {
+            MsgLikeKind::Message(message) => message.body().to_string(),
+            MsgLikeKind::Sticker(sticker) => sticker.content().body.clone(),
+            MsgLikeKind::Poll(_) => "poll".to_string(),
+            MsgLikeKind::Redacted => Default::default(),
+            MsgLikeKind::UnableToDecrypt(_) => "utd".to_string(),
+            MsgLikeKind::Other(other) => format!("{:?}", other),
+        },
+        event => format!("{:?}", event),
+    }
 }
 
 fn init(matrix_id: String, password: String) -> Box<Connection> {
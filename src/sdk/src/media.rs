@@ -0,0 +1,193 @@
+// SPDX-FileCopyrightText: 2026 Tobias Fella <tobias.fella@kde.org>
+// SPDX-License-Identifier: LGPL-2.0-or-later
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::SystemTime;
+
+use matrix_sdk::media::{MediaFormat, MediaRequestParameters, MediaThumbnailSettings};
+use matrix_sdk::ruma::events::room::MediaSource;
+use matrix_sdk::ruma::media::Method;
+use matrix_sdk::ruma::OwnedMxcUri;
+use matrix_sdk::Client;
+
+use crate::task::token;
+
+/// Default size limit of the media cache: 500 MiB
+const DEFAULT_MAX_SIZE: u64 = 500 * 1024 * 1024;
+
+/// Suffix of files that are still being written.
+const PARTIAL_SUFFIX: &str = ".part";
+
+/// On-disk cache for downloaded media, bounded in size.
+///
+/// When the cache grows above its limit, the least recently used files are removed. A file's modification time is
+/// its last use, since it is updated whenever the file is requested.
+pub(crate) struct MediaCache {
+    dir: PathBuf,
+    max_size: AtomicU64,
+}
+
+impl MediaCache {
+    /// The cache of this process. It is shared between all accounts, since media is identified by its URI.
+    pub(crate) fn shared() -> Arc<MediaCache> {
+        static CACHE: OnceLock<Arc<MediaCache>> = OnceLock::new();
+        CACHE
+            .get_or_init(|| {
+                Arc::new(MediaCache {
+                    dir: dirs::cache_dir()
+                        .unwrap()
+                        .join("Arctic")
+                        .join("monster")
+                        .join("media"),
+                    max_size: AtomicU64::new(DEFAULT_MAX_SIZE),
+                })
+            })
+            .clone()
+    }
+
+    /// Set the size limit; call [`MediaCache::evict`] afterwards to apply it.
+    pub(crate) fn set_max_size(&self, max_size: u64) {
+        self.max_size.store(max_size, Ordering::Relaxed);
+    }
+
+    pub(crate) async fn clear(&self) {
+        let dir = self.dir.clone();
+        let _ = tokio::task::spawn_blocking(move || std::fs::remove_dir_all(dir)).await;
+    }
+
+    /// Return the cached file for `request`, downloading it if it isn't cached yet.
+    pub(crate) async fn fetch(
+        &self,
+        client: &Client,
+        request: &MediaRequestParameters,
+    ) -> matrix_sdk::Result<PathBuf> {
+        let name = file_name(request);
+        let path = self.dir.join(&name);
+        if tokio::fs::try_exists(&path).await.unwrap_or_default() {
+            touch(path.clone()).await;
+            return Ok(path);
+        }
+        let data = client.media().get_media_content(request, false).await?;
+        tokio::fs::create_dir_all(&self.dir).await?;
+        // Write to a temporary file first, so that concurrent requests never see a partially written file
+        let partial = self
+            .dir
+            .join(format!("{}.{}{}", name, token(), PARTIAL_SUFFIX));
+        if let Err(error) = tokio::fs::write(&partial, data).await {
+            let _ = tokio::fs::remove_file(&partial).await;
+            return Err(error.into());
+        }
+        tokio::fs::rename(&partial, &path).await?;
+        self.evict(Some(path.clone())).await;
+        Ok(path)
+    }
+
+    /// Remove the least recently used files until the cache fits into its size limit, never removing `keep`.
+    pub(crate) async fn evict(&self, keep: Option<PathBuf>) {
+        let dir = self.dir.clone();
+        let max_size = self.max_size.load(Ordering::Relaxed);
+        let _ = tokio::task::spawn_blocking(move || evict(&dir, max_size, keep.as_deref())).await;
+    }
+}
+
+/// Mark the file as used now, see [`MediaCache`].
+async fn touch(path: PathBuf) {
+    let _ = tokio::task::spawn_blocking(move || {
+        std::fs::File::options()
+            .write(true)
+            .open(path)?
+            .set_modified(SystemTime::now())
+    })
+    .await;
+}
+
+fn evict(dir: &Path, max_size: u64, keep: Option<&Path>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut files: Vec<(PathBuf, u64, SystemTime)> = entries
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let metadata = entry.metadata().ok()?;
+            Some((entry.path(), metadata.len(), metadata.modified().ok()?))
+        })
+        .collect();
+    files.sort_by_key(|(_, _, modified)| *modified);
+
+    let mut size: u64 = files.iter().map(|(_, size, _)| size).sum();
+    for (path, file_size, _) in files {
+        if size <= max_size {
+            break;
+        }
+        let partial = path.to_string_lossy().ends_with(PARTIAL_SUFFIX);
+        if partial || Some(path.as_path()) == keep {
+            continue;
+        }
+        if std::fs::remove_file(path).is_ok() {
+            size -= file_size;
+        }
+    }
+}
+
+/// Parse a media source from C++, either a plain `mxc://` URI or the JSON of a [`MediaSource`].
+pub(crate) fn media_source(source: &str) -> Result<MediaSource, String> {
+    if source.starts_with("mxc://") {
+        Ok(MediaSource::Plain(source.into()))
+    } else {
+        serde_json::from_str(source).map_err(|error| error.to_string())
+    }
+}
+
+/// Download an avatar, as thumbnail if `width` and `height` are not 0.
+///
+/// Avatars are small, so they are kept in the SDK's media store instead of the [`MediaCache`].
+pub(crate) async fn avatar(
+    client: &Client,
+    url: OwnedMxcUri,
+    width: u32,
+    height: u32,
+) -> Option<Vec<u8>> {
+    let format = if width == 0 || height == 0 {
+        MediaFormat::File
+    } else {
+        MediaFormat::Thumbnail(MediaThumbnailSettings::new(width.into(), height.into()))
+    };
+    let request = MediaRequestParameters {
+        source: MediaSource::Plain(url),
+        format,
+    };
+    client.media().get_media_content(&request, true).await.ok()
+}
+
+/// Thumbnail resizing method. 0: Scale, 1: Crop
+pub(crate) fn method(method: u8) -> Method {
+    match method {
+        1 => Method::Crop,
+        _ => Method::Scale,
+    }
+}
+
+/// Name of the cache file for `request`, derived from the media's URI and the requested format.
+fn file_name(request: &MediaRequestParameters) -> String {
+    let uri = match &request.source {
+        MediaSource::Plain(uri) => uri.to_string(),
+        MediaSource::Encrypted(file) => file.url.to_string(),
+    };
+    let uri: String = uri
+        .trim_start_matches("mxc://")
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    match &request.format {
+        MediaFormat::File => uri,
+        MediaFormat::Thumbnail(settings) => format!(
+            "{}-{}x{}-{}",
+            uri,
+            settings.width,
+            settings.height,
+            settings.method.as_str()
+        ),
+    }
+}
@@ -0,0 +1,272 @@
+// SPDX-FileCopyrightText: 2026 Tobias Fella <tobias.fella@kde.org>
+// SPDX-License-Identifier: LGPL-2.0-or-later
+
+use std::sync::{Arc, RwLock};
+
+use eyeball_im::VectorDiff;
+use matrix_sdk::room::RoomMember;
+use matrix_sdk::ruma::events::room::member::{MembershipState, SyncRoomMemberEvent};
+use matrix_sdk::ruma::presence::PresenceState;
+use matrix_sdk::ruma::events::room::power_levels::SyncRoomPowerLevelsEvent;
+use matrix_sdk::ruma::events::room::power_levels::UserPowerLevel;
+use matrix_sdk::ruma::OwnedUserId;
+use matrix_sdk::RoomMemberships;
+use tokio::sync::mpsc;
+use tokio::task::AbortHandle;
+
+use crate::connection::Connection;
+use crate::ffi::ffi;
+
+pub struct Member(pub RoomMember);
+
+impl Member {
+    pub fn user_id(&self) -> String {
+        self.0.user_id().to_string()
+    }
+
+    /// The member's display name, falling back to their user id.
+    pub fn display_name(&self) -> String {
+        self.0.name().to_string()
+    }
+
+    pub fn avatar_url(&self) -> String {
+        self.0
+            .avatar_url()
+            .map(|url| url.to_string())
+            .unwrap_or_default()
+    }
+
+    /// 0: Joined, 1: Left, 2: Invited, 3: Knocked, 4: Banned
+    pub fn membership(&self) -> u8 {
+        match self.0.membership() {
+            MembershipState::Join => 0,
+            MembershipState::Invite => 2,
+            MembershipState::Knock => 3,
+            MembershipState::Ban => 4,
+            _ => 1,
+        }
+    }
+
+    /// The member's power level; `i64::MAX` for room creators, whose power level is infinite.
+    pub fn power_level(&self) -> i64 {
+        match self.0.power_level() {
+            UserPowerLevel::Infinite => i64::MAX,
+            UserPowerLevel::Int(power_level) => power_level.into(),
+            _ => 0,
+        }
+    }
+
+    /// 0: User, 1: Moderator, 2: Administrator
+    pub fn role(&self) -> u8 {
+        match self.power_level() {
+            100.. => 2,
+            50.. => 1,
+            _ => 0,
+        }
+    }
+
+    /// Whether the local user ignores this member.
+    pub fn is_ignored(&self) -> bool {
+        self.0.is_ignored()
+    }
+
+    /// The member's presence as last received through sync.
+    ///
+    /// 0: Unknown, 1: Online, 2: Unavailable, 3: Offline
+    pub fn presence(&self, connection: &Connection) -> u8 {
+        let client = connection.client.clone();
+        let user_id = self.0.user_id().to_owned();
+        let event = connection.rt.block_on(async move {
+            client
+                .state_store()
+                .get_presence_event(&user_id)
+                .await
+                .ok()
+                .flatten()
+        });
+        match event.and_then(|event| event.deserialize().ok()) {
+            Some(event) => match event.content.presence {
+                PresenceState::Online => 1,
+                PresenceState::Unavailable => 2,
+                PresenceState::Offline => 3,
+                _ => 0,
+            },
+            None => 0,
+        }
+    }
+
+    pub fn box_me(&self) -> Box<Member> {
+        Box::new(Member(self.0.clone()))
+    }
+}
+
+/// The joined and invited members of a room, delivered as a stream of diffs.
+///
+/// Changes are announced through `shim_members_changed`. Dropping this stops the updates.
+pub struct Members {
+    queue: Arc<RwLock<Vec<VectorDiff<RoomMember>>>>,
+    task: AbortHandle,
+}
+
+pub struct MemberListVecDiff(VectorDiff<RoomMember>);
+
+impl Members {
+    pub(crate) fn new(room: matrix_sdk::room::Room, connection: &Connection) -> Box<Members> {
+        let queue = Arc::new(RwLock::new(vec![]));
+        let queue_clone = queue.clone();
+        let task = connection.rt.spawn(async move {
+            let queue = queue_clone;
+            let matrix_id = room.own_user_id().to_string();
+            let room_id = room.room_id().to_string();
+
+            // Some(user) if a member changed, None if all members need to be reloaded
+            let (sender, mut receiver) = mpsc::unbounded_channel::<Option<OwnedUserId>>();
+            let member_sender = sender.clone();
+            let member_handle = room.add_event_handler(move |event: SyncRoomMemberEvent| {
+                let _ = member_sender.send(Some(event.state_key().to_owned()));
+                async {}
+            });
+            let power_levels_handle = room.add_event_handler(move |_: SyncRoomPowerLevelsEvent| {
+                let _ = sender.send(None);
+                async {}
+            });
+            let _member_guard = room.client().event_handler_drop_guard(member_handle);
+            let _power_levels_guard = room.client().event_handler_drop_guard(power_levels_handle);
+
+            // Lazy loading only gives us the members that sent events we've seen, so load all of them
+            let _ = room.sync_members().await;
+            let members = room
+                .members(RoomMemberships::ACTIVE)
+                .await
+                .unwrap_or_default();
+            let mut user_ids: Vec<OwnedUserId> = members
+                .iter()
+                .map(|member| member.user_id().to_owned())
+                .collect();
+            queue.write().unwrap().push(VectorDiff::Append {
+                values: members.into_iter().collect(),
+            });
+            ffi::shim_members_changed(matrix_id.clone(), room_id.clone());
+
+            while let Some(change) = receiver.recv().await {
+                let diff = match change {
+                    None => {
+                        let members = room
+                            .members_no_sync(RoomMemberships::ACTIVE)
+                            .await
+                            .unwrap_or_default();
+                        user_ids = members
+                            .iter()
+                            .map(|member| member.user_id().to_owned())
+                            .collect();
+                        VectorDiff::Reset {
+                            values: members.into_iter().collect(),
+                        }
+                    }
+                    Some(user_id) => {
+                        let member = room
+                            .get_member_no_sync(&user_id)
+                            .await
+                            .ok()
+                            .flatten()
+                            .filter(|member| {
+                                matches!(
+                                    member.membership(),
+                                    MembershipState::Join | MembershipState::Invite
+                                )
+                            });
+                        let index = user_ids.iter().position(|id| *id == user_id);
+                        match (index, member) {
+                            (Some(index), Some(member)) => VectorDiff::Set {
+                                index,
+                                value: member,
+                            },
+                            (Some(index), None) => {
+                                user_ids.remove(index);
+                                VectorDiff::Remove { index }
+                            }
+                            (None, Some(member)) => {
+                                user_ids.push(user_id);
+                                VectorDiff::PushBack { value: member }
+                            }
+                            (None, None) => continue,
+                        }
+                    }
+                };
+                queue.write().unwrap().push(diff);
+                ffi::shim_members_changed(matrix_id.clone(), room_id.clone());
+            }
+        });
+        Box::new(Members {
+            queue,
+            task: task.abort_handle(),
+        })
+    }
+
+    pub fn has_queued_item(&self) -> bool {
+        !self.queue.read().unwrap().is_empty()
+    }
+
+    pub fn queue_next(&self) -> Box<MemberListVecDiff> {
+        let mut write = self.queue.write().unwrap();
+        let item = Box::new(MemberListVecDiff(write.first().unwrap().clone()));
+        write.remove(0);
+        item
+    }
+}
+
+impl Drop for Members {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl MemberListVecDiff {
+    pub fn op(&self) -> u8 {
+        match self.0 {
+            VectorDiff::Append { .. } => 0,
+            VectorDiff::Clear => 1,
+            VectorDiff::PushFront { .. } => 2,
+            VectorDiff::PushBack { .. } => 3,
+            VectorDiff::PopFront => 4,
+            VectorDiff::PopBack => 5,
+            VectorDiff::Insert { .. } => 6,
+            VectorDiff::Set { .. } => 7,
+            VectorDiff::Remove { .. } => 8,
+            VectorDiff::Truncate { .. } => 9,
+            VectorDiff::Reset { .. } => 10,
+        }
+    }
+
+    pub fn index(&self) -> usize {
+        match self.0 {
+            VectorDiff::Insert { index, .. } => index,
+            VectorDiff::Set { index, .. } => index,
+            VectorDiff::Remove { index, .. } => index,
+            VectorDiff::Truncate { length, .. } => length,
+            _ => panic!(),
+        }
+    }
+
+    pub fn item(&self) -> Box<Member> {
+        match &self.0 {
+            VectorDiff::Insert { value, .. } => Box::new(Member(value.clone())),
+            VectorDiff::Set { value, .. } => Box::new(Member(value.clone())),
+            VectorDiff::PushFront { value, .. } => Box::new(Member(value.clone())),
+            VectorDiff::PushBack { value, .. } => Box::new(Member(value.clone())),
+            _ => panic!(),
+        }
+    }
+
+    pub fn items_vec(&self) -> Vec<Member> {
+        match &self.0 {
+            VectorDiff::Append { values, .. } => {
+                values.iter().map(|member| Member(member.clone())).collect()
+            }
+            VectorDiff::Reset { values, .. } => {
+                values.iter().map(|member| Member(member.clone())).collect()
+            }
+            _ => panic!(),
+        }
+    }
+}
@@ -0,0 +1,156 @@
+// SPDX-FileCopyrightText: 2026 Tobias Fella <tobias.fella@kde.org>
+// SPDX-License-Identifier: LGPL-2.0-or-later
+
+use std::sync::Arc;
+
+use matrix_sdk::deserialized_responses::RawAnySyncOrStrippedTimelineEvent;
+use matrix_sdk::ruma::events::room::message::SyncRoomMessageEvent;
+use matrix_sdk::ruma::events::{AnySyncMessageLikeEvent, AnySyncTimelineEvent};
+use matrix_sdk::ruma::{OwnedEventId, OwnedRoomId};
+use matrix_sdk::Client;
+use matrix_sdk_ui::notification_client::{
+    NotificationClient, NotificationEvent, NotificationItem, NotificationProcessSetup,
+    NotificationStatus,
+};
+use matrix_sdk_ui::sync_service::SyncService;
+
+use crate::ffi::ffi;
+
+/// Maximum number of characters of the message included in a notification.
+const PREVIEW_LENGTH: usize = 200;
+
+/// An event that the push rules say the user should be notified about.
+pub struct Notification {
+    room_id: OwnedRoomId,
+    event_id: OwnedEventId,
+    item: NotificationItem,
+}
+
+impl Notification {
+    pub fn room_id(&self) -> String {
+        self.room_id.to_string()
+    }
+
+    pub fn event_id(&self) -> String {
+        self.event_id.to_string()
+    }
+
+    pub fn room_name(&self) -> String {
+        self.item.room_computed_display_name.clone()
+    }
+
+    pub fn sender_id(&self) -> String {
+        match &self.item.event {
+            NotificationEvent::Timeline(event) => event.sender().to_string(),
+            NotificationEvent::Invite(event) => event.sender.to_string(),
+        }
+    }
+
+    /// The sender's display name, falling back to their user id.
+    pub fn sender_name(&self) -> String {
+        self.item
+            .sender_display_name
+            .clone()
+            .unwrap_or_else(|| self.sender_id())
+    }
+
+    /// The beginning of the message; empty for invites and events that can't be decrypted.
+    pub fn body(&self) -> String {
+        let NotificationEvent::Timeline(event) = &self.item.event else {
+            return Default::default();
+        };
+        match event.as_ref() {
+            AnySyncTimelineEvent::MessageLike(AnySyncMessageLikeEvent::RoomMessage(
+                SyncRoomMessageEvent::Original(event),
+            )) => event.content.body().chars().take(PREVIEW_LENGTH).collect(),
+            _ => Default::default(),
+        }
+    }
+
+    /// Whether the user or the whole room is mentioned.
+    pub fn is_mention(&self) -> bool {
+        self.item.has_mention.unwrap_or_default()
+    }
+
+    /// Whether the push rules ask for a sound to be played.
+    pub fn is_noisy(&self) -> bool {
+        self.item.is_noisy.unwrap_or_default()
+    }
+
+    pub fn is_invite(&self) -> bool {
+        matches!(self.item.event, NotificationEvent::Invite(_))
+    }
+
+    pub fn is_direct(&self) -> bool {
+        self.item.is_direct_message_room
+    }
+}
+
+/// Resolve the event into a notification, decrypting it if needed.
+///
+/// Returns `None` if the event can't be found or the push rules say not to notify for it.
+pub(crate) async fn notification(
+    notification_client: &NotificationClient,
+    room_id: OwnedRoomId,
+    event_id: OwnedEventId,
+) -> Result<Option<Notification>, String> {
+    let status = notification_client
+        .get_notification(&room_id, &event_id)
+        .await
+        .map_err(|error| error.to_string())?;
+    let NotificationStatus::Event(item) = status else {
+        return Ok(None);
+    };
+    Ok(Some(Notification {
+        room_id,
+        event_id,
+        item: *item,
+    }))
+}
+
+/// Report every event the push rules notify for through `shim_notification_received`.
+///
+/// The sync only tells us that an event notifies; the [`NotificationClient`] then retries decrypting it and collects
+/// the sender and room information.
+pub(crate) async fn listen(client: Client, sync_service: Arc<SyncService>) {
+    let matrix_id = client
+        .user_id()
+        .map(|it| it.to_string())
+        .unwrap_or_default();
+    let notification_client = match NotificationClient::new(
+        client.clone(),
+        NotificationProcessSetup::SingleProcess { sync_service },
+    )
+    .await
+    {
+        Ok(notification_client) => Arc::new(notification_client),
+        Err(error) => {
+            eprintln!("Failed to set up notifications: {:?}", error);
+            return;
+        }
+    };
+    client
+        .register_notification_handler(move |notification, room: matrix_sdk::room::Room, _| {
+            let notification_client = notification_client.clone();
+            let matrix_id = matrix_id.clone();
+            async move {
+                let event_id = match &notification.event {
+                    RawAnySyncOrStrippedTimelineEvent::Sync(raw) => raw.get_field("event_id"),
+                    RawAnySyncOrStrippedTimelineEvent::Stripped(raw) => raw.get_field("event_id"),
+                };
+                let Ok(Some(event_id)) = event_id else {
+                    return;
+                };
+                match self::notification(&notification_client, room.room_id().to_owned(), event_id)
+                    .await
+                {
+                    Ok(Some(notification)) => {
+                        ffi::shim_notification_received(matrix_id, Box::new(notification))
+                    }
+                    Ok(None) => {}
+                    Err(error) => eprintln!("Failed to load notification: {}", error),
+                }
+            }
+        })
+        .await;
+}
@@ -0,0 +1,49 @@
+// SPDX-FileCopyrightText: 2026 Tobias Fella <tobias.fella@kde.org>
+// SPDX-License-Identifier: LGPL-2.0-or-later
+
+use matrix_sdk::notification_settings::RoomNotificationMode;
+use matrix_sdk::Room;
+
+/// 0: All messages, 1: Mentions and keywords only, 2: Mute
+pub(crate) fn mode_to_u8(mode: RoomNotificationMode) -> u8 {
+    match mode {
+        RoomNotificationMode::AllMessages => 0,
+        RoomNotificationMode::MentionsAndKeywordsOnly => 1,
+        RoomNotificationMode::Mute => 2,
+    }
+}
+
+/// See [`mode_to_u8`].
+pub(crate) fn mode_from_u8(mode: u8) -> RoomNotificationMode {
+    match mode {
+        1 => RoomNotificationMode::MentionsAndKeywordsOnly,
+        2 => RoomNotificationMode::Mute,
+        _ => RoomNotificationMode::AllMessages,
+    }
+}
+
+/// Whether "mentions and keywords only" works in `room`.
+///
+/// The server can't see mentions in encrypted events. It can only leave the decision to this device if it pushes
+/// encrypted events to it instead of notifying for all of them.
+pub(crate) async fn supports_mentions_only(room: &Room) -> bool {
+    !room.encryption_state().is_encrypted()
+        || room
+            .client()
+            .notification_settings()
+            .await
+            .can_push_encrypted_event_to_device()
+            .await
+}
+
+/// The notification mode that actually applies to `room`.
+///
+/// In encrypted rooms where "mentions and keywords only" doesn't work, the server notifies for all messages.
+pub(crate) async fn effective_mode(room: &Room) -> Option<RoomNotificationMode> {
+    match room.notification_mode().await? {
+        RoomNotificationMode::MentionsAndKeywordsOnly if !supports_mentions_only(room).await => {
+            Some(RoomNotificationMode::AllMessages)
+        }
+        mode => Some(mode),
+    }
+}
@@ -0,0 +1,70 @@
+// SPDX-FileCopyrightText: 2026 Tobias Fella <tobias.fella@kde.org>
+// SPDX-License-Identifier: LGPL-2.0-or-later
+
+use matrix_sdk::ruma::api::client::push::{PusherIds, PusherKind};
+use matrix_sdk::ruma::push::PushFormat;
+use matrix_sdk::ruma::{OwnedEventId, OwnedRoomId};
+use serde_json::Value;
+
+/// A pusher registered for the account, which makes the homeserver forward notifications to a push gateway.
+pub struct Pusher(pub matrix_sdk::ruma::api::client::push::Pusher);
+
+impl Pusher {
+    pub fn app_id(&self) -> String {
+        self.0.ids.app_id.clone()
+    }
+
+    pub fn push_key(&self) -> String {
+        self.0.ids.pushkey.clone()
+    }
+
+    pub fn app_display_name(&self) -> String {
+        self.0.app_display_name.clone()
+    }
+
+    pub fn device_display_name(&self) -> String {
+        self.0.device_display_name.clone()
+    }
+
+    /// The URL of the push gateway; empty for email pushers.
+    pub fn url(&self) -> String {
+        match &self.0.kind {
+            PusherKind::Http(data) => data.url.clone(),
+            _ => Default::default(),
+        }
+    }
+
+    /// The format of the pushed notifications, e.g. `event_id_only`; empty for the full format.
+    pub fn format(&self) -> String {
+        match &self.0.kind {
+            PusherKind::Http(data) => data
+                .format
+                .as_ref()
+                .map(|format| format.to_string())
+                .unwrap_or_default(),
+            _ => Default::default(),
+        }
+    }
+}
+
+pub(crate) fn ids(app_id: String, push_key: String) -> PusherIds {
+    PusherIds::new(push_key, app_id)
+}
+
+/// See [`Pusher::format`].
+pub(crate) fn format(format: String) -> Option<PushFormat> {
+    (!format.is_empty()).then(|| PushFormat::from(format))
+}
+
+/// Find the room and event a push is about.
+///
+/// `payload` is the JSON body the push gateway received from the homeserver, as delivered e.g. by UnifiedPush.
+pub(crate) fn event(payload: &str) -> Result<(OwnedRoomId, OwnedEventId), String> {
+    let payload: Value = serde_json::from_str(payload).map_err(|error| error.to_string())?;
+    let notification = &payload["notification"];
+    let room_id = serde_json::from_value(notification["room_id"].clone())
+        .map_err(|error| error.to_string())?;
+    let event_id = serde_json::from_value(notification["event_id"].clone())
+        .map_err(|error| error.to_string())?;
+    Ok((room_id, event_id))
+}
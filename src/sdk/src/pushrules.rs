@@ -0,0 +1,38 @@
+// SPDX-FileCopyrightText: 2026 Tobias Fella <tobias.fella@kde.org>
+// SPDX-License-Identifier: LGPL-2.0-or-later
+
+use matrix_sdk::ruma::push::{
+    PredefinedContentRuleId, PredefinedOverrideRuleId, PredefinedUnderrideRuleId, RuleKind,
+};
+
+/// The server default push rules that make up one user-facing setting.
+///
+/// 0: Mentions, 1: Direct messages, 2: Group messages, 3: Invites, 4: Calls, 5: Room upgrades
+// The legacy mention rules are deprecated, but servers still apply them to messages without `m.mentions`
+#[allow(deprecated)]
+pub(crate) fn rules(rule: u8) -> Vec<(RuleKind, String)> {
+    let override_rule = |id: PredefinedOverrideRuleId| (RuleKind::Override, id.to_string());
+    let content_rule = |id: PredefinedContentRuleId| (RuleKind::Content, id.to_string());
+    let underride_rule = |id: PredefinedUnderrideRuleId| (RuleKind::Underride, id.to_string());
+    match rule {
+        0 => vec![
+            override_rule(PredefinedOverrideRuleId::IsUserMention),
+            override_rule(PredefinedOverrideRuleId::IsRoomMention),
+            override_rule(PredefinedOverrideRuleId::ContainsDisplayName),
+            override_rule(PredefinedOverrideRuleId::RoomNotif),
+            content_rule(PredefinedContentRuleId::ContainsUserName),
+        ],
+        1 => vec![
+            underride_rule(PredefinedUnderrideRuleId::RoomOneToOne),
+            underride_rule(PredefinedUnderrideRuleId::EncryptedRoomOneToOne),
+        ],
+        2 => vec![
+            underride_rule(PredefinedUnderrideRuleId::Message),
+            underride_rule(PredefinedUnderrideRuleId::Encrypted),
+        ],
+        3 => vec![override_rule(PredefinedOverrideRuleId::InviteForMe)],
+        4 => vec![underride_rule(PredefinedUnderrideRuleId::Call)],
+        5 => vec![override_rule(PredefinedOverrideRuleId::Tombstone)],
+        _ => vec![],
+    }
+}
@@ -0,0 +1,88 @@
+// SPDX-FileCopyrightText: 2026 Tobias Fella <tobias.fella@kde.org>
+// SPDX-License-Identifier: LGPL-2.0-or-later
+
+use matrix_sdk::ruma::OwnedUserId;
+use matrix_sdk_ui::timeline::{ReactionInfo, ReactionStatus};
+
+/// All reactions to an event using the same key.
+pub struct Reaction {
+    key: String,
+    senders: Vec<ReactionSender>,
+    own_sender: Option<usize>,
+}
+
+/// A single user's reaction.
+#[derive(Clone)]
+pub struct ReactionSender {
+    user_id: String,
+    timestamp: u64,
+    sent: bool,
+}
+
+impl Reaction {
+    pub(crate) fn new<'a>(
+        key: &str,
+        senders: impl Iterator<Item = (&'a OwnedUserId, &'a ReactionInfo)>,
+        own_user_id: &str,
+    ) -> Reaction {
+        let senders: Vec<ReactionSender> = senders
+            .map(|(user_id, info)| ReactionSender {
+                user_id: user_id.to_string(),
+                timestamp: info.timestamp.0.into(),
+                sent: matches!(info.status, ReactionStatus::RemoteToRemote(_)),
+            })
+            .collect();
+        let own_sender = senders
+            .iter()
+            .position(|sender| sender.user_id == own_user_id);
+        Reaction {
+            key: key.to_string(),
+            senders,
+            own_sender,
+        }
+    }
+
+    pub fn key(&self) -> String {
+        self.key.clone()
+    }
+
+    pub fn count(&self) -> usize {
+        self.senders.len()
+    }
+
+    /// Whether the local user is among the senders of this reaction.
+    pub fn reacted_by_me(&self) -> bool {
+        self.own_sender.is_some()
+    }
+
+    /// Send state of the local user's reaction.
+    ///
+    /// 0: Not reacted, 1: Local echo that hasn't been acknowledged by the server yet, 2: Sent
+    pub fn send_state(&self) -> u8 {
+        match self.own_sender.map(|index| &self.senders[index]) {
+            None => 0,
+            Some(sender) if !sender.sent => 1,
+            Some(_) => 2,
+        }
+    }
+
+    pub fn senders(&self) -> Vec<ReactionSender> {
+        self.senders.clone()
+    }
+}
+
+impl ReactionSender {
+    pub fn user_id(&self) -> String {
+        self.user_id.clone()
+    }
+
+    /// Time the reaction was sent, in milliseconds since the unix epoch.
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    /// Whether the reaction has been acknowledged by the server.
+    pub fn is_sent(&self) -> bool {
+        self.sent
+    }
+}
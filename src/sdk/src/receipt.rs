@@ -0,0 +1,30 @@
+// SPDX-FileCopyrightText: 2026 Tobias Fella <tobias.fella@kde.org>
+// SPDX-License-Identifier: LGPL-2.0-or-later
+
+use matrix_sdk::ruma::api::client::receipt::create_receipt::v3::ReceiptType;
+
+/// A user's read receipt on an event.
+pub struct ReadReceipt {
+    pub(crate) user_id: String,
+    pub(crate) timestamp: u64,
+}
+
+impl ReadReceipt {
+    pub fn user_id(&self) -> String {
+        self.user_id.clone()
+    }
+
+    /// Time the receipt was sent, in milliseconds since the unix epoch; 0 if unknown.
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+}
+
+/// 0: Public read receipt, 1: Private read receipt, 2: Fully read marker
+pub(crate) fn receipt_type(receipt_type: u8) -> ReceiptType {
+    match receipt_type {
+        1 => ReceiptType::ReadPrivate,
+        2 => ReceiptType::FullyRead,
+        _ => ReceiptType::Read,
+    }
+}
@@ -0,0 +1,51 @@
+// SPDX-FileCopyrightText: 2026 Tobias Fella <tobias.fella@kde.org>
+// SPDX-License-Identifier: LGPL-2.0-or-later
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use matrix_sdk::event_handler::EventHandlerDropGuard;
+use matrix_sdk::room::Room;
+use matrix_sdk::ruma::events::room::redaction::SyncRoomRedactionEvent;
+use matrix_sdk::ruma::{EventId, OwnedEventId};
+
+/// Who redacted an event and why.
+#[derive(Clone)]
+pub(crate) struct Redaction {
+    pub(crate) sender: String,
+    pub(crate) reason: String,
+}
+
+/// The redactions a timeline received while it was open.
+///
+/// When an event is redacted live, the timeline only replaces its content; the event's JSON doesn't get a
+/// `redacted_because`, so the redaction has to be remembered separately.
+#[derive(Clone, Default)]
+pub(crate) struct Redactions(Arc<Mutex<HashMap<OwnedEventId, Redaction>>>);
+
+impl Redactions {
+    pub(crate) fn get(&self, event_id: &EventId) -> Option<Redaction> {
+        self.0.lock().unwrap().get(event_id).cloned()
+    }
+
+    /// Remember the redactions sent to `room` until the returned guard is dropped.
+    pub(crate) fn watch(&self, room: &Room) -> EventHandlerDropGuard {
+        let redactions = self.clone();
+        let handle = room.add_event_handler(move |event: SyncRoomRedactionEvent| {
+            if let SyncRoomRedactionEvent::Original(event) = event {
+                // Since room version 11, the redacted event is part of the content
+                if let Some(redacts) = event.content.redacts.or(event.redacts) {
+                    redactions.0.lock().unwrap().insert(
+                        redacts,
+                        Redaction {
+                            sender: event.sender.to_string(),
+                            reason: event.content.reason.unwrap_or_default(),
+                        },
+                    );
+                }
+            }
+            async {}
+        });
+        room.client().event_handler_drop_guard(handle)
+    }
+}
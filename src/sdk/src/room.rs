@@ -2,10 +2,29 @@
 // SPDX-License-Identifier: LGPL-2.0-or-later
 
 use matrix_sdk::{
-    ruma::room::RoomType,
-    RoomState,
+    ruma::{
+        api::client::room::aliases as get_local_aliases,
+        events::{
+            receipt::ReceiptThread,
+            space::{child::SpaceChildEventContent, parent::SpaceParentEventContent},
+            tag::{TagInfo, TagName},
+            StateEventType,
+        },
+        room::RoomType, EventId, OwnedServerName, RoomAliasId, RoomId, UserId,
+    },
+    notification_settings::RoomNotificationMode,
+    HttpError, RoomMemberships, RoomState,
 };
+use tokio::sync::broadcast::error::RecvError;
 
+use crate::connection::{server_names, Connection};
+use crate::ffi::ffi;
+use crate::members::{Member, Members};
+use crate::notificationsettings;
+use crate::receipt;
+use crate::tag;
+use crate::sendqueue::{self, PendingEvent};
+use crate::task::{self, Subscription};
 use crate::tombstone::RoomTombstoneEventContent;
 
 pub struct Room {
@@ -88,4 +107,530 @@ impl Room {
     pub fn is_low_priority(&self) -> bool {
         self.room.is_low_priority()
     }
+
+    /// Redact the event with the given id, with an optional reason.
+    pub fn redact(&self, connection: &Connection, event_id: String, reason: String) -> String {
+        let room = self.room.clone();
+        task::spawn(&connection.rt, async move {
+            let event_id = EventId::parse(event_id).map_err(|error| error.to_string())?;
+            let reason = (!reason.is_empty()).then_some(reason);
+            room.redact(&event_id, reason.as_deref(), None)
+                .await
+                .map_err(|error| error.to_string())
+        })
+    }
+
+    /// Whether the room's send queue is sending events.
+    ///
+    /// The send queue is disabled automatically when sending an event fails with a recoverable error.
+    pub fn is_send_queue_enabled(&self) -> bool {
+        self.room.send_queue().is_enabled()
+    }
+
+    pub fn set_send_queue_enabled(&self, enabled: bool) {
+        self.room.send_queue().set_enabled(enabled);
+    }
+
+    /// The events in this room's send queue that haven't been sent yet.
+    pub fn pending_events(&self, connection: &Connection) -> Vec<PendingEvent> {
+        connection
+            .rt
+            .block_on(sendqueue::pending_events(&self.room))
+    }
+
+    /// Send a receipt for the event with the given id.
+    ///
+    /// `receipt_type` is 0 for a public read receipt, 1 for a private one and 2 for the fully read marker.
+    pub fn send_receipt(&self, connection: &Connection, event_id: String, receipt_type: u8) -> String {
+        let room = self.room.clone();
+        task::spawn(&connection.rt, async move {
+            let event_id = EventId::parse(event_id).map_err(|error| error.to_string())?;
+            room.send_single_receipt(
+                receipt::receipt_type(receipt_type),
+                ReceiptThread::Unthreaded,
+                event_id,
+            )
+            .await
+            .map_err(|error| error.to_string())
+        })
+    }
+
+    /// Whether the room has been marked as unread manually ([MSC2867]).
+    ///
+    /// [MSC2867]: https://github.com/matrix-org/matrix-spec-proposals/pull/2867
+    pub fn is_marked_unread(&self) -> bool {
+        self.room.is_marked_unread()
+    }
+
+    pub fn set_marked_unread(&self, connection: &Connection, unread: bool) -> String {
+        let room = self.room.clone();
+        task::spawn(&connection.rt, async move { room.set_unread_flag(unread).await })
+    }
+
+    /// Notify the room that the local user started or stopped typing.
+    ///
+    /// While typing, this can be called repeatedly, e.g. on every key press; the notice is only resent when the
+    /// previous one is about to time out. If no stop notice is sent, the server times it out after a few seconds.
+    pub fn typing_notice(&self, connection: &Connection, typing: bool) -> String {
+        let room = self.room.clone();
+        task::spawn(&connection.rt, async move { room.typing_notice(typing).await })
+    }
+
+    /// Deliver the users currently typing in this room through `shim_typing_users_changed` whenever they change.
+    ///
+    /// The local user is not included. Dropping the returned subscription stops the updates.
+    pub fn subscribe_to_typing(&self, connection: &Connection) -> Box<Subscription> {
+        let room = self.room.clone();
+        let handle = connection.rt.spawn(async move {
+            let (_guard, mut receiver) = room.subscribe_to_typing_notifications();
+            let own_user_id = room.own_user_id().to_owned();
+            loop {
+                let user_ids = match receiver.recv().await {
+                    Ok(user_ids) => user_ids,
+                    // Only the latest typing users matter, so missed updates can be skipped
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => break,
+                };
+                let mut ids = Vec::new();
+                let mut display_names = Vec::new();
+                for user_id in user_ids.into_iter().filter(|id| *id != own_user_id) {
+                    let display_name = match room.get_member_no_sync(&user_id).await {
+                        Ok(Some(member)) => member.name().to_string(),
+                        _ => user_id.to_string(),
+                    };
+                    ids.push(user_id.to_string());
+                    display_names.push(display_name);
+                }
+                ffi::shim_typing_users_changed(
+                    own_user_id.to_string(),
+                    room.room_id().to_string(),
+                    ids,
+                    display_names,
+                );
+            }
+        });
+        Box::new(Subscription(handle.abort_handle()))
+    }
+
+    /// Accept an invite to this room.
+    pub fn accept_invite(&self, connection: &Connection) -> String {
+        let room = self.room.clone();
+        task::spawn(&connection.rt, async move { room.join().await })
+    }
+
+    /// Decline an invite to this room.
+    pub fn decline_invite(&self, connection: &Connection) -> String {
+        let room = self.room.clone();
+        task::spawn(&connection.rt, async move { room.leave().await })
+    }
+
+    pub fn leave(&self, connection: &Connection) -> String {
+        let room = self.room.clone();
+        task::spawn(&connection.rt, async move { room.leave().await })
+    }
+
+    /// Forget a room that has been left, removing it from the room list.
+    pub fn forget(&self, connection: &Connection) -> String {
+        let room = self.room.clone();
+        task::spawn(&connection.rt, async move { room.forget().await })
+    }
+
+    pub fn invite_user(&self, connection: &Connection, user_id: String) -> String {
+        let room = self.room.clone();
+        task::spawn(&connection.rt, async move {
+            let user_id = UserId::parse(user_id).map_err(|error| error.to_string())?;
+            room.invite_user_by_id(&user_id)
+                .await
+                .map_err(|error| error.to_string())
+        })
+    }
+
+    /// Remove a user from the room, with an optional reason.
+    pub fn kick(&self, connection: &Connection, user_id: String, reason: String) -> String {
+        let room = self.room.clone();
+        task::spawn(&connection.rt, async move {
+            let user_id = UserId::parse(user_id).map_err(|error| error.to_string())?;
+            let reason = (!reason.is_empty()).then_some(reason);
+            room.kick_user(&user_id, reason.as_deref())
+                .await
+                .map_err(|error| error.to_string())
+        })
+    }
+
+    /// Ban a user from the room, with an optional reason.
+    pub fn ban(&self, connection: &Connection, user_id: String, reason: String) -> String {
+        let room = self.room.clone();
+        task::spawn(&connection.rt, async move {
+            let user_id = UserId::parse(user_id).map_err(|error| error.to_string())?;
+            let reason = (!reason.is_empty()).then_some(reason);
+            room.ban_user(&user_id, reason.as_deref())
+                .await
+                .map_err(|error| error.to_string())
+        })
+    }
+
+    /// Lift the ban of a user, with an optional reason.
+    pub fn unban(&self, connection: &Connection, user_id: String, reason: String) -> String {
+        let room = self.room.clone();
+        task::spawn(&connection.rt, async move {
+            let user_id = UserId::parse(user_id).map_err(|error| error.to_string())?;
+            let reason = (!reason.is_empty()).then_some(reason);
+            room.unban_user(&user_id, reason.as_deref())
+                .await
+                .map_err(|error| error.to_string())
+        })
+    }
+
+    /// Whether the local user's power level allows inviting users.
+    pub fn can_invite(&self, connection: &Connection) -> bool {
+        connection
+            .rt
+            .block_on(self.room.can_user_invite(self.room.own_user_id()))
+            .unwrap_or_default()
+    }
+
+    /// Whether the local user's power level allows kicking users.
+    pub fn can_kick(&self, connection: &Connection) -> bool {
+        connection
+            .rt
+            .block_on(self.room.can_user_kick(self.room.own_user_id()))
+            .unwrap_or_default()
+    }
+
+    /// Whether the local user's power level allows banning and unbanning users.
+    pub fn can_ban(&self, connection: &Connection) -> bool {
+        connection
+            .rt
+            .block_on(self.room.can_user_ban(self.room.own_user_id()))
+            .unwrap_or_default()
+    }
+
+    /// Whether the local user's power level allows redacting events of other users.
+    pub fn can_redact_others(&self, connection: &Connection) -> bool {
+        connection
+            .rt
+            .block_on(self.room.can_user_redact_other(self.room.own_user_id()))
+            .unwrap_or_default()
+    }
+
+    /// Subscribe to the joined and invited members of this room, loading all of them from the server.
+    pub fn members(&self, connection: &Connection) -> Box<Members> {
+        Members::new(self.room.clone(), connection)
+    }
+
+    /// Joined and invited members whose display name or user id contains `query`, ignoring case.
+    pub fn search_members(&self, connection: &Connection, query: String) -> Vec<Member> {
+        let query = query.to_lowercase();
+        connection
+            .rt
+            .block_on(self.room.members_no_sync(RoomMemberships::ACTIVE))
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|member| {
+                member.name().to_lowercase().contains(&query)
+                    || member.user_id().as_str().to_lowercase().contains(&query)
+            })
+            .map(Member)
+            .collect()
+    }
+
+    pub fn set_name(&self, connection: &Connection, name: String) -> String {
+        let room = self.room.clone();
+        task::spawn(&connection.rt, async move { room.set_name(name).await })
+    }
+
+    pub fn set_topic(&self, connection: &Connection, topic: String) -> String {
+        let room = self.room.clone();
+        task::spawn(&connection.rt, async move { room.set_room_topic(&topic).await })
+    }
+
+    /// Upload the image at `path` and make it the room's avatar. An empty path removes the avatar.
+    pub fn set_avatar(&self, connection: &Connection, path: String) -> String {
+        let room = self.room.clone();
+        task::spawn(&connection.rt, async move {
+            if path.is_empty() {
+                return room
+                    .remove_avatar()
+                    .await
+                    .map(|_| ())
+                    .map_err(|error| error.to_string());
+            }
+            let data = tokio::fs::read(&path)
+                .await
+                .map_err(|error| error.to_string())?;
+            let content_type = mime_guess::from_path(&path).first_or_octet_stream();
+            room.upload_avatar(&content_type, data, None)
+                .await
+                .map(|_| ())
+                .map_err(|error| error.to_string())
+        })
+    }
+
+    pub fn canonical_alias(&self) -> String {
+        self.room
+            .canonical_alias()
+            .map(|alias| alias.to_string())
+            .unwrap_or_default()
+    }
+
+    /// The alternative aliases from the room's `m.room.canonical_alias` event.
+    pub fn alt_aliases(&self) -> Vec<String> {
+        self.room
+            .alt_aliases()
+            .iter()
+            .map(|alias| alias.to_string())
+            .collect()
+    }
+
+    /// Replace the room's canonical and alternative aliases. An empty `alias` removes the canonical alias.
+    ///
+    /// The aliases must already point to this room.
+    pub fn set_canonical_alias(
+        &self,
+        connection: &Connection,
+        alias: String,
+        alt_aliases: Vec<String>,
+    ) -> String {
+        let room = self.room.clone();
+        task::spawn(&connection.rt, async move {
+            let alias = if alias.is_empty() {
+                None
+            } else {
+                Some(RoomAliasId::parse(alias).map_err(|error| error.to_string())?)
+            };
+            let alt_aliases = alt_aliases
+                .into_iter()
+                .map(RoomAliasId::parse)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|error| error.to_string())?;
+            room.privacy_settings()
+                .update_canonical_alias(alias, alt_aliases)
+                .await
+                .map_err(|error| error.to_string())
+        })
+    }
+
+    /// Load the aliases pointing to this room that were created on the local user's server.
+    ///
+    /// The aliases are reported through `shim_local_aliases_loaded` with the task's token.
+    pub fn local_aliases(&self, connection: &Connection) -> String {
+        let room = self.room.clone();
+        connection.tasks.spawn(&connection.rt, move |token| async move {
+            let request = get_local_aliases::v3::Request::new(room.room_id().to_owned());
+            let response = room.client().send(request).await?;
+            ffi::shim_local_aliases_loaded(
+                token,
+                response.aliases.iter().map(|alias| alias.to_string()).collect(),
+            );
+            Ok::<(), HttpError>(())
+        })
+    }
+
+    /// Create the alias `alias` on its server, pointing to this room.
+    pub fn add_local_alias(&self, connection: &Connection, alias: String) -> String {
+        let room = self.room.clone();
+        task::spawn(&connection.rt, async move {
+            let alias = RoomAliasId::parse(alias).map_err(|error| error.to_string())?;
+            room.client()
+                .create_room_alias(&alias, room.room_id())
+                .await
+                .map_err(|error| error.to_string())
+        })
+    }
+
+    /// Delete the alias `alias` from its server.
+    pub fn remove_local_alias(&self, connection: &Connection, alias: String) -> String {
+        let room = self.room.clone();
+        task::spawn(&connection.rt, async move {
+            let alias = RoomAliasId::parse(alias).map_err(|error| error.to_string())?;
+            room.client()
+                .remove_room_alias(&alias)
+                .await
+                .map_err(|error| error.to_string())
+        })
+    }
+
+    /// Add or remove the `m.favourite` tag. A negative `order` sets no order.
+    pub fn set_favourite(&self, connection: &Connection, favourite: bool, order: f64) -> String {
+        let room = self.room.clone();
+        task::spawn(&connection.rt, async move {
+            room.set_is_favourite(favourite, tag::order(order)).await
+        })
+    }
+
+    /// Add or remove the `m.lowpriority` tag. A negative `order` sets no order.
+    pub fn set_low_priority(&self, connection: &Connection, low_priority: bool, order: f64) -> String {
+        let room = self.room.clone();
+        task::spawn(&connection.rt, async move {
+            room.set_is_low_priority(low_priority, tag::order(order)).await
+        })
+    }
+
+    /// Add the tag `name`, e.g. `u.work`, or update its order. A negative `order` sets no order.
+    pub fn set_tag(&self, connection: &Connection, name: String, order: f64) -> String {
+        let room = self.room.clone();
+        task::spawn(&connection.rt, async move {
+            let mut info = TagInfo::new();
+            info.order = tag::order(order);
+            room.set_tag(TagName::from(name.as_str()), info).await
+        })
+    }
+
+    pub fn remove_tag(&self, connection: &Connection, name: String) -> String {
+        let room = self.room.clone();
+        task::spawn(&connection.rt, async move {
+            room.remove_tag(TagName::from(name.as_str())).await
+        })
+    }
+
+    /// Whether the room is a direct chat according to the `m.direct` account data.
+    pub fn is_direct(&self, connection: &Connection) -> bool {
+        connection
+            .rt
+            .block_on(self.room.is_direct())
+            .unwrap_or_default()
+    }
+
+    /// The users this room is a direct chat with.
+    pub fn direct_targets(&self) -> Vec<String> {
+        self.room
+            .direct_targets()
+            .iter()
+            .map(|user_id| user_id.to_string())
+            .collect()
+    }
+
+    /// The effective notification mode of the room, either set for this room or the default for rooms like it.
+    ///
+    /// 0: All messages, 1: Mentions and keywords only, 2: Mute
+    pub fn notification_mode(&self, connection: &Connection) -> u8 {
+        connection
+            .rt
+            .block_on(notificationsettings::effective_mode(&self.room))
+            .map(notificationsettings::mode_to_u8)
+            .unwrap_or_default()
+    }
+
+    /// Whether "mentions and keywords only" can be used in this room. In encrypted rooms, this needs support from
+    /// the server.
+    pub fn supports_mentions_only(&self, connection: &Connection) -> bool {
+        connection
+            .rt
+            .block_on(notificationsettings::supports_mentions_only(&self.room))
+    }
+
+    /// Whether the notification mode was set for this room, instead of using the default.
+    pub fn has_custom_notification_mode(&self) -> bool {
+        self.room.cached_user_defined_notification_mode().is_some()
+    }
+
+    /// Set the notification mode of this room. See [`Room::notification_mode`] for the values.
+    ///
+    /// Fails for "mentions and keywords only" if the room doesn't support it, see [`Room::supports_mentions_only`].
+    pub fn set_notification_mode(&self, connection: &Connection, mode: u8) -> String {
+        let room = self.room.clone();
+        task::spawn(&connection.rt, async move {
+            let mode = notificationsettings::mode_from_u8(mode);
+            if mode == RoomNotificationMode::MentionsAndKeywordsOnly
+                && !notificationsettings::supports_mentions_only(&room).await
+            {
+                return Err(
+                    "The server can't notify only for mentions in this encrypted room".to_string(),
+                );
+            }
+            room.client()
+                .notification_settings()
+                .await
+                .set_room_notification_mode(room.room_id(), mode)
+                .await
+                .map_err(|error| error.to_string())
+        })
+    }
+
+    /// Use the default notification mode for this room again.
+    pub fn reset_notification_mode(&self, connection: &Connection) -> String {
+        let room = self.room.clone();
+        task::spawn(&connection.rt, async move {
+            room.client()
+                .notification_settings()
+                .await
+                .delete_user_defined_room_rules(room.room_id())
+                .await
+        })
+    }
+
+    /// Add `room_id` as child of this space.
+    ///
+    /// `via` are servers through which the child can be joined; if empty, the local user's server is used. `order` is
+    /// used for sorting the children; empty for no order.
+    pub fn add_child(
+        &self,
+        connection: &Connection,
+        room_id: String,
+        via: Vec<String>,
+        order: String,
+        suggested: bool,
+    ) -> String {
+        let room = self.room.clone();
+        task::spawn(&connection.rt, async move {
+            let room_id = RoomId::parse(room_id).map_err(|error| error.to_string())?;
+            let mut content = SpaceChildEventContent::new(via_or_own_server(&room, via)?);
+            content.order = if order.is_empty() {
+                None
+            } else {
+                Some(order.try_into().map_err(|error| error.to_string())?)
+            };
+            content.suggested = suggested;
+            room.send_state_event_for_key(&room_id, content)
+                .await
+                .map_err(|error| error.to_string())
+        })
+    }
+
+    /// Remove `room_id` from the children of this space.
+    pub fn remove_child(&self, connection: &Connection, room_id: String) -> String {
+        let room = self.room.clone();
+        task::spawn(&connection.rt, async move {
+            // Children are removed by replacing their state event with one without content
+            room.send_state_event_raw(
+                StateEventType::SpaceChild.to_string().as_str(),
+                &room_id,
+                serde_json::json!({}),
+            )
+            .await
+            .map_err(|error| error.to_string())
+        })
+    }
+
+    /// Set the space `space_id` as the main space this room belongs to.
+    ///
+    /// `via` are servers through which the space can be joined; if empty, the local user's server is used.
+    pub fn set_canonical_parent(
+        &self,
+        connection: &Connection,
+        space_id: String,
+        via: Vec<String>,
+    ) -> String {
+        let room = self.room.clone();
+        task::spawn(&connection.rt, async move {
+            let space_id = RoomId::parse(space_id).map_err(|error| error.to_string())?;
+            let mut content = SpaceParentEventContent::new(via_or_own_server(&room, via)?);
+            content.canonical = true;
+            room.send_state_event_for_key(&space_id, content)
+                .await
+                .map_err(|error| error.to_string())
+        })
+    }
+}
+
+/// Parse the given server names, falling back to the local user's server.
+fn via_or_own_server(
+    room: &matrix_sdk::room::Room,
+    via: Vec<String>,
+) -> Result<Vec<OwnedServerName>, String> {
+    if via.is_empty() {
+        Ok(vec![room.own_user_id().server_name().to_owned()])
+    } else {
+        server_names(via)
+    }
 }
@@ -0,0 +1,191 @@
+// SPDX-FileCopyrightText: 2026 Tobias Fella <tobias.fella@kde.org>
+// SPDX-License-Identifier: LGPL-2.0-or-later
+
+use matrix_sdk::ruma::api::client::room::create_room::v3::{CreationContent, Request, RoomPreset};
+use matrix_sdk::ruma::api::client::room::Visibility;
+use matrix_sdk::ruma::events::room::encryption::RoomEncryptionEventContent;
+use matrix_sdk::ruma::events::room::guest_access::{GuestAccess, RoomGuestAccessEventContent};
+use matrix_sdk::ruma::events::room::history_visibility::{
+    HistoryVisibility, RoomHistoryVisibilityEventContent,
+};
+use matrix_sdk::ruma::events::room::join_rules::{JoinRule, RoomJoinRulesEventContent};
+use matrix_sdk::ruma::events::InitialStateEvent;
+use matrix_sdk::ruma::room::RoomType;
+use matrix_sdk::ruma::serde::Raw;
+use matrix_sdk::ruma::{RoomVersionId, UserId};
+
+/// Options for creating a room.
+///
+/// Invalid values don't fail immediately; instead, creating the room fails with the first error.
+#[derive(Clone)]
+pub struct RoomCreateOptions {
+    request: Request,
+    encrypted: bool,
+    history_visibility: Option<HistoryVisibility>,
+    join_rule: Option<JoinRule>,
+    guest_access: Option<GuestAccess>,
+    error: Option<String>,
+}
+
+impl RoomCreateOptions {
+    pub fn set_invite(&mut self, users: Vec<String>) {
+        let users: Result<_, _> = users.iter().map(UserId::parse).collect();
+        match users {
+            Ok(users) => self.request.invite = users,
+            Err(error) => self.fail(error.to_string()),
+        }
+    }
+
+    pub fn set_name(&mut self, name: String) {
+        self.request.name = Some(name);
+    }
+
+    pub fn set_room_alias(&mut self, alias: String) {
+        self.request.room_alias_name = Some(alias);
+    }
+
+    pub fn set_topic(&mut self, topic: String) {
+        self.request.topic = Some(topic);
+    }
+
+    pub fn set_visibility_public(&mut self, visibility_public: bool) {
+        self.request.visibility = if visibility_public {
+            Visibility::Public
+        } else {
+            Visibility::Private
+        };
+    }
+
+    /// Create a space instead of a room.
+    pub fn set_space(&mut self, space: bool) {
+        let mut content = CreationContent::new();
+        if space {
+            content.room_type = Some(RoomType::Space);
+        }
+        match Raw::new(&content) {
+            Ok(content) => self.request.creation_content = Some(content),
+            Err(error) => self.fail(error.to_string()),
+        }
+    }
+
+    /// The preset determining the default join rules, history visibility and power levels.
+    ///
+    /// 0: Private chat, 1: Public chat, 2: Trusted private chat
+    pub fn set_preset(&mut self, preset: u8) {
+        self.request.preset = Some(match preset {
+            1 => RoomPreset::PublicChat,
+            2 => RoomPreset::TrustedPrivateChat,
+            _ => RoomPreset::PrivateChat,
+        });
+    }
+
+    /// Mark the room as direct chat with the invited users.
+    pub fn set_is_direct(&mut self, is_direct: bool) {
+        self.request.is_direct = is_direct;
+    }
+
+    /// The room version, e.g. "11"; the server's default is used if this isn't set.
+    pub fn set_room_version(&mut self, room_version: String) {
+        match RoomVersionId::try_from(room_version) {
+            Ok(room_version) => self.request.room_version = Some(room_version),
+            Err(error) => self.fail(error.to_string()),
+        }
+    }
+
+    /// Enable end-to-end encryption from the start.
+    pub fn set_encrypted(&mut self, encrypted: bool) {
+        self.encrypted = encrypted;
+    }
+
+    /// 0: Shared, 1: Invited, 2: Joined, 3: World readable
+    pub fn set_history_visibility(&mut self, history_visibility: u8) {
+        self.history_visibility = Some(match history_visibility {
+            1 => HistoryVisibility::Invited,
+            2 => HistoryVisibility::Joined,
+            3 => HistoryVisibility::WorldReadable,
+            _ => HistoryVisibility::Shared,
+        });
+    }
+
+    /// 0: Invite, 1: Public, 2: Knock
+    pub fn set_join_rule(&mut self, join_rule: u8) {
+        self.join_rule = Some(match join_rule {
+            1 => JoinRule::Public,
+            2 => JoinRule::Knock,
+            _ => JoinRule::Invite,
+        });
+    }
+
+    pub fn set_guest_access(&mut self, guest_access: bool) {
+        self.guest_access = Some(if guest_access {
+            GuestAccess::CanJoin
+        } else {
+            GuestAccess::Forbidden
+        });
+    }
+
+    /// Override parts of the default power levels, given as JSON of an `m.room.power_levels` event's content.
+    pub fn set_power_level_override(&mut self, power_levels: String) {
+        match Raw::from_json_string(power_levels) {
+            Ok(power_levels) => self.request.power_level_content_override = Some(power_levels),
+            Err(error) => self.fail(error.to_string()),
+        }
+    }
+
+    /// The request for creating the room, or the first error in the options.
+    pub(crate) fn request(&self) -> Result<Request, String> {
+        if let Some(error) = &self.error {
+            return Err(error.clone());
+        }
+        let mut request = self.request.clone();
+        if self.encrypted {
+            request.initial_state.push(
+                InitialStateEvent::with_empty_state_key(
+                    RoomEncryptionEventContent::with_recommended_defaults(),
+                )
+                .to_raw_any(),
+            );
+        }
+        if let Some(history_visibility) = &self.history_visibility {
+            request.initial_state.push(
+                InitialStateEvent::with_empty_state_key(RoomHistoryVisibilityEventContent::new(
+                    history_visibility.clone(),
+                ))
+                .to_raw_any(),
+            );
+        }
+        if let Some(join_rule) = &self.join_rule {
+            request.initial_state.push(
+                InitialStateEvent::with_empty_state_key(RoomJoinRulesEventContent::new(
+                    join_rule.clone(),
+                ))
+                .to_raw_any(),
+            );
+        }
+        if let Some(guest_access) = &self.guest_access {
+            request.initial_state.push(
+                InitialStateEvent::with_empty_state_key(RoomGuestAccessEventContent::new(
+                    guest_access.clone(),
+                ))
+                .to_raw_any(),
+            );
+        }
+        Ok(request)
+    }
+
+    /// Remember the first error, to be reported when creating the room.
+    fn fail(&mut self, error: String) {
+        self.error.get_or_insert(error);
+    }
+}
+
+pub fn room_create_options_new() -> Box<RoomCreateOptions> {
+    Box::new(RoomCreateOptions {
+        request: Request::new(),
+        encrypted: false,
+        history_visibility: None,
+        join_rule: None,
+        guest_access: None,
+        error: None,
+    })
+}
@@ -4,6 +4,9 @@
 use matrix_sdk::RoomState;
 use matrix_sdk::ruma::room::RoomType;
 
+use crate::connection::Connection;
+use crate::notificationsettings;
+use crate::tag::Tag;
 use crate::tombstone::RoomTombstoneEventContent;
 
 pub struct RoomListItem(pub matrix_sdk_ui::room_list_service::RoomListItem);
@@ -94,6 +97,56 @@ impl RoomListItem {
         self.0.is_low_priority()
     }
 
+    /// Whether the room has been marked as unread manually ([MSC2867]).
+    ///
+    /// [MSC2867]: https://github.com/matrix-org/matrix-spec-proposals/pull/2867
+    pub fn is_marked_unread(&self) -> bool {
+        self.0.is_marked_unread()
+    }
+
+    /// All tags of the room, including `m.favourite` and `m.lowpriority`.
+    pub fn tags(&self, connection: &Connection) -> Vec<Tag> {
+        connection
+            .rt
+            .block_on(self.0.tags())
+            .ok()
+            .flatten()
+            .map(|tags| {
+                tags.into_iter()
+                    .map(|(name, info)| Tag::new(name, info))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Whether the room is a direct chat according to the `m.direct` account data.
+    pub fn is_direct(&self, connection: &Connection) -> bool {
+        connection
+            .rt
+            .block_on(self.0.is_direct())
+            .unwrap_or_default()
+    }
+
+    /// The users this room is a direct chat with.
+    pub fn direct_targets(&self) -> Vec<String> {
+        self.0
+            .direct_targets()
+            .iter()
+            .map(|user_id| user_id.to_string())
+            .collect()
+    }
+
+    /// The effective notification mode of the room.
+    ///
+    /// 0: All messages, 1: Mentions and keywords only, 2: Mute
+    pub fn notification_mode(&self, connection: &Connection) -> u8 {
+        connection
+            .rt
+            .block_on(notificationsettings::effective_mode(&self.0))
+            .map(notificationsettings::mode_to_u8)
+            .unwrap_or_default()
+    }
+
     pub fn box_me(&self) -> Box<RoomListItem> {
         Box::new(RoomListItem(self.0.clone()))
     }
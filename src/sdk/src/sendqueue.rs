@@ -0,0 +1,142 @@
+// SPDX-FileCopyrightText: 2026 Tobias Fella <tobias.fella@kde.org>
+// SPDX-License-Identifier: LGPL-2.0-or-later
+
+use matrix_sdk::room::Room;
+use matrix_sdk::ruma::{OwnedRoomId, TransactionId};
+use matrix_sdk::send_queue::{LocalEcho, LocalEchoContent, RoomSendQueueUpdate, SendHandle};
+use tokio::runtime::Handle;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::broadcast::Receiver;
+
+use crate::ffi::ffi;
+
+/// An event waiting in a room's send queue.
+pub struct PendingEvent {
+    room_id: OwnedRoomId,
+    echo: LocalEcho,
+}
+
+impl PendingEvent {
+    pub fn room_id(&self) -> String {
+        self.room_id.to_string()
+    }
+
+    pub fn transaction_id(&self) -> String {
+        self.echo.transaction_id.to_string()
+    }
+
+    /// Type of the event, e.g. `m.room.message`; `m.reaction` for reactions.
+    pub fn event_type(&self) -> String {
+        match &self.echo.content {
+            LocalEchoContent::Event {
+                serialized_event, ..
+            } => serialized_event
+                .deserialize()
+                .map(|content| content.event_type().to_string())
+                .unwrap_or_default(),
+            LocalEchoContent::React { .. } => "m.reaction".to_string(),
+        }
+    }
+
+    /// The error that stopped this event from being sent, or an empty string.
+    pub fn error(&self) -> String {
+        match &self.echo.content {
+            LocalEchoContent::Event {
+                send_error: Some(error),
+                ..
+            } => error.to_string(),
+            _ => Default::default(),
+        }
+    }
+}
+
+/// The events in the room's send queue that haven't been sent yet.
+pub(crate) async fn pending_events(room: &Room) -> Vec<PendingEvent> {
+    let Ok((echoes, _)) = room.send_queue().subscribe().await else {
+        return Vec::new();
+    };
+    echoes
+        .into_iter()
+        .map(|echo| PendingEvent {
+            room_id: room.room_id().to_owned(),
+            echo,
+        })
+        .collect()
+}
+
+/// Find the handle of the local echo with the given transaction id in the room's send queue.
+pub(crate) async fn send_handle(room: &Room, transaction_id: &TransactionId) -> Option<SendHandle> {
+    let (echoes, _) = room.send_queue().subscribe().await.ok()?;
+    echoes
+        .into_iter()
+        .find(|echo| echo.transaction_id == transaction_id)
+        .and_then(|echo| match echo.content {
+            LocalEchoContent::Event { send_handle, .. } => Some(send_handle),
+            _ => None,
+        })
+}
+
+/// Removes the event from the send queue when dropped before being disarmed.
+struct AbortOnDrop(Option<SendHandle>);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        if let (Some(handle), Ok(rt)) = (self.0.take(), Handle::try_current()) {
+            rt.spawn(async move {
+                let _ = handle.abort().await;
+            });
+        }
+    }
+}
+
+/// Wait until the event of `handle` is sent, reporting the progress of its media uploads to the task `token`.
+///
+/// `updates` has to be subscribed before the event is queued. Errors the send queue retries by itself keep the
+/// task running. If the task is cancelled, the event is removed from the send queue.
+pub(crate) async fn wait_until_sent(
+    handle: SendHandle,
+    mut updates: Receiver<RoomSendQueueUpdate>,
+    token: String,
+) -> Result<(), String> {
+    let transaction_id = handle.transaction_id().to_owned();
+    let mut guard = AbortOnDrop(Some(handle));
+    loop {
+        let update = match updates.recv().await {
+            Ok(update) => update,
+            Err(RecvError::Lagged(_)) => continue,
+            Err(RecvError::Closed) => return Err("The send queue was closed".to_string()),
+        };
+        match update {
+            RoomSendQueueUpdate::MediaUpload {
+                related_to,
+                progress,
+                ..
+            } if related_to == transaction_id => {
+                ffi::task_progress(token.clone(), progress.current as u64, progress.total as u64);
+            }
+            RoomSendQueueUpdate::SentEvent {
+                transaction_id: sent,
+                ..
+            } if sent == transaction_id => {
+                guard.0 = None;
+                return Ok(());
+            }
+            RoomSendQueueUpdate::SendError {
+                transaction_id: failed,
+                error,
+                is_recoverable: false,
+            } if failed == transaction_id => {
+                // The event stays in the send queue, so that it can be retried
+                guard.0 = None;
+                return Err(error.to_string());
+            }
+            RoomSendQueueUpdate::CancelledLocalEvent {
+                transaction_id: cancelled,
+            } if cancelled == transaction_id => {
+                guard.0 = None;
+                return Err("Sending was cancelled".to_string());
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,210 @@
+// SPDX-FileCopyrightText: 2026 Tobias Fella <tobias.fella@kde.org>
+// SPDX-License-Identifier: LGPL-2.0-or-later
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use matrix_sdk::ruma::api::client::space::{get_hierarchy, SpaceHierarchyRoomsChunk};
+use matrix_sdk::ruma::room::{JoinRuleSummary, RoomType};
+use matrix_sdk::ruma::{OwnedRoomId, UInt};
+use matrix_sdk::{Client, HttpError, RoomState};
+
+use crate::connection::Connection;
+use crate::ffi::ffi;
+
+/// Number of rooms requested per page.
+const PAGE_SIZE: u32 = 50;
+
+/// A room in a space's hierarchy, which the local user might not have joined.
+pub struct SpaceHierarchyRoom {
+    chunk: SpaceHierarchyRoomsChunk,
+    parent_id: Option<OwnedRoomId>,
+    suggested: bool,
+    membership: u8,
+}
+
+impl SpaceHierarchyRoom {
+    pub fn room_id(&self) -> String {
+        self.chunk.summary.room_id.to_string()
+    }
+
+    /// The space this room was found in; empty for the space the hierarchy was requested for.
+    pub fn parent_id(&self) -> String {
+        self.parent_id
+            .as_ref()
+            .map(|id| id.to_string())
+            .unwrap_or_default()
+    }
+
+    pub fn name(&self) -> String {
+        self.chunk.summary.name.clone().unwrap_or_default()
+    }
+
+    pub fn topic(&self) -> String {
+        self.chunk.summary.topic.clone().unwrap_or_default()
+    }
+
+    pub fn avatar_url(&self) -> String {
+        self.chunk
+            .summary
+            .avatar_url
+            .as_ref()
+            .map(|url| url.to_string())
+            .unwrap_or_default()
+    }
+
+    pub fn canonical_alias(&self) -> String {
+        self.chunk
+            .summary
+            .canonical_alias
+            .as_ref()
+            .map(|alias| alias.to_string())
+            .unwrap_or_default()
+    }
+
+    pub fn num_joined_members(&self) -> u64 {
+        self.chunk.summary.num_joined_members.into()
+    }
+
+    /// 0: Invite, 1: Public, 2: Knock, 3: Restricted, 4: Knock restricted, 5: Private or unknown
+    pub fn join_rule(&self) -> u8 {
+        match self.chunk.summary.join_rule {
+            JoinRuleSummary::Invite => 0,
+            JoinRuleSummary::Public => 1,
+            JoinRuleSummary::Knock => 2,
+            JoinRuleSummary::Restricted(_) => 3,
+            JoinRuleSummary::KnockRestricted(_) => 4,
+            _ => 5,
+        }
+    }
+
+    pub fn is_space(&self) -> bool {
+        self.chunk.summary.room_type == Some(RoomType::Space)
+    }
+
+    /// Whether the parent space suggests joining this room.
+    pub fn is_suggested(&self) -> bool {
+        self.suggested
+    }
+
+    /// The local user's membership in this room.
+    ///
+    /// 0: Joined, 1: Left or never joined, 2: Invited, 3: Knocked, 4: Banned
+    pub fn membership(&self) -> u8 {
+        self.membership
+    }
+}
+
+#[derive(Default)]
+struct State {
+    next_batch: Option<String>,
+    at_end: bool,
+    /// Token of the page request in flight
+    loading: Option<String>,
+    /// Parent and suggested flag of the rooms announced by the spaces loaded so far
+    children: HashMap<OwnedRoomId, (OwnedRoomId, bool)>,
+}
+
+/// Marks the page request as finished when dropped, also if it is cancelled.
+struct Loading(Arc<Mutex<State>>);
+
+impl Drop for Loading {
+    fn drop(&mut self) {
+        self.0.lock().unwrap().loading = None;
+    }
+}
+
+/// The rooms in a space and its subspaces, loaded from the server page by page.
+pub struct SpaceHierarchy {
+    space_id: OwnedRoomId,
+    state: Arc<Mutex<State>>,
+}
+
+impl SpaceHierarchy {
+    pub(crate) fn new(space_id: OwnedRoomId) -> Box<SpaceHierarchy> {
+        Box::new(SpaceHierarchy {
+            space_id,
+            state: Default::default(),
+        })
+    }
+
+    /// Load the next page of rooms; they are delivered through `shim_space_hierarchy_loaded`.
+    ///
+    /// If a page is already being loaded, returns its token instead of loading another one. Returns an empty string if
+    /// all rooms were loaded.
+    pub fn paginate(&self, connection: &Connection) -> String {
+        let mut running = self.state.lock().unwrap();
+        if let Some(token) = running.loading.as_ref() {
+            return token.clone();
+        }
+        if running.at_end {
+            return Default::default();
+        }
+        let client = connection.client.clone();
+        let space_id = self.space_id.clone();
+        let state = self.state.clone();
+        let loading = Loading(self.state.clone());
+        let token = connection.tasks.spawn(&connection.rt, move |token| {
+            load_page(client, space_id, state, loading, token)
+        });
+        running.loading = Some(token.clone());
+        token
+    }
+
+    /// Whether all rooms were loaded.
+    pub fn at_end(&self) -> bool {
+        self.state.lock().unwrap().at_end
+    }
+}
+
+/// Load the next page for [`SpaceHierarchy::paginate`].
+async fn load_page(
+    client: Client,
+    space_id: OwnedRoomId,
+    state: Arc<Mutex<State>>,
+    loading: Loading,
+    token: String,
+) -> Result<(), HttpError> {
+    let mut request = get_hierarchy::v1::Request::new(space_id);
+    request.from = state.lock().unwrap().next_batch.clone();
+    request.limit = Some(UInt::from(PAGE_SIZE));
+    let response = client.send(request).await?;
+
+    let mut state = state.lock().unwrap();
+    state.at_end = response.next_batch.is_none();
+    state.next_batch = response.next_batch;
+    let mut rooms = Vec::new();
+    for chunk in response.rooms {
+        let room_id = chunk.summary.room_id.clone();
+        for child in &chunk.children_state {
+            let Ok(child) = child.deserialize() else {
+                continue;
+            };
+            state
+                .children
+                .insert(child.state_key, (room_id.clone(), child.content.suggested));
+        }
+        let (parent_id, suggested) = match state.children.get(&room_id) {
+            Some((parent_id, suggested)) => (Some(parent_id.clone()), *suggested),
+            None => (None, false),
+        };
+        let membership = match client.get_room(&room_id).map(|room| room.state()) {
+            Some(RoomState::Joined) => 0,
+            Some(RoomState::Invited) => 2,
+            Some(RoomState::Knocked) => 3,
+            Some(RoomState::Banned) => 4,
+            _ => 1,
+        };
+        rooms.push(SpaceHierarchyRoom {
+            chunk,
+            parent_id,
+            suggested,
+            membership,
+        });
+    }
+    drop(state);
+    // Finish before reporting, so that the next page can be requested right away
+    drop(loading);
+    ffi::shim_space_hierarchy_loaded(token, rooms);
+    Ok(())
+}
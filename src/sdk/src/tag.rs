@@ -0,0 +1,30 @@
+// SPDX-FileCopyrightText: 2026 Tobias Fella <tobias.fella@kde.org>
+// SPDX-License-Identifier: LGPL-2.0-or-later
+
+use matrix_sdk::ruma::events::tag::{TagInfo, TagName};
+
+/// A tag of a room, e.g. `m.favourite` or a user-defined `u.*` tag.
+pub struct Tag {
+    name: TagName,
+    info: TagInfo,
+}
+
+impl Tag {
+    pub(crate) fn new(name: TagName, info: TagInfo) -> Tag {
+        Tag { name, info }
+    }
+
+    pub fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    /// The position of the room among the rooms with this tag, between 0 and 1; -1 if there is none.
+    pub fn order(&self) -> f64 {
+        self.info.order.unwrap_or(-1.0)
+    }
+}
+
+/// Convert an order from C++ to a tag order, where negative values mean no order.
+pub(crate) fn order(order: f64) -> Option<f64> {
+    (order >= 0.0).then_some(order)
+}
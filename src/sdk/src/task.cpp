@@ -10,8 +10,29 @@ Task::Task(const QString &token, QObject *parent)
     , m_token(token)
 {
     //TODO connectUntil, or use Task as receiver and delete task;
-    connect(Dispatcher::instance(), &Dispatcher::taskDone, this, [this, token] {
+    connect(Dispatcher::instance(), &Dispatcher::taskDone, this, [this, token](const QString &doneToken) {
+        if (doneToken != token) {
+            return;
+        }
         Q_EMIT done();
         deleteLater();
     });
+    connect(Dispatcher::instance(), &Dispatcher::taskFailed, this, [this, token](const QString &failedToken, const QString &error) {
+        if (failedToken != token) {
+            return;
+        }
+        Q_EMIT failed(error);
+        deleteLater();
+    });
+    connect(Dispatcher::instance(), &Dispatcher::taskProgress, this, [this, token](const QString &progressToken, quint64 current, quint64 total) {
+        if (progressToken != token) {
+            return;
+        }
+        Q_EMIT progress(current, total);
+    });
+}
+
+QString Task::token() const
+{
+    return m_token;
 }
\ No newline at end of file
@@ -14,8 +14,12 @@ class Task : public QObject
 public:
     explicit Task(const QString &token, QObject *parent = nullptr);
 
+    QString token() const;
+
 Q_SIGNALS:
     void done();
+    void failed(const QString &error);
+    void progress(quint64 current, quint64 total);
 private:
     QString m_token;
 };
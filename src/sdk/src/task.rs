@@ -0,0 +1,98 @@
+// SPDX-FileCopyrightText: 2026 Tobias Fella <tobias.fella@kde.org>
+// SPDX-License-Identifier: LGPL-2.0-or-later
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use tokio::runtime::Runtime;
+use tokio::task::AbortHandle;
+
+use crate::ffi::ffi;
+
+pub(crate) fn token() -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(16)
+        .map(char::from)
+        .collect()
+}
+
+/// Run `future` on `rt` as a task that C++ can follow.
+///
+/// Returns the token identifying the task. Once the future completes, either `task_done` or
+/// `task_failed` is called with that token.
+pub(crate) fn spawn<F, T, E>(rt: &Runtime, future: F) -> String
+where
+    F: Future<Output = Result<T, E>> + Send + 'static,
+    T: Send + 'static,
+    E: Display + Send + 'static,
+{
+    let token = token();
+    let token_clone = token.clone();
+    rt.spawn(async move {
+        match future.await {
+            Ok(_) => ffi::task_done(token_clone),
+            Err(error) => ffi::task_failed(token_clone, error.to_string()),
+        }
+    });
+    token
+}
+
+/// Keeps a background task that reports updates to C++ running until dropped.
+pub struct Subscription(pub(crate) AbortHandle);
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// Tasks that can be cancelled from C++ using their token.
+#[derive(Clone, Default)]
+pub(crate) struct CancellableTasks(Arc<Mutex<HashMap<String, AbortHandle>>>);
+
+impl CancellableTasks {
+    /// Like [`spawn`], but the task can be aborted using [`CancellableTasks::cancel`].
+    ///
+    /// `f` receives the token of the task, e.g. for reporting progress.
+    pub(crate) fn spawn<F, Fut, T, E>(&self, rt: &Runtime, f: F) -> String
+    where
+        F: FnOnce(String) -> Fut,
+        Fut: Future<Output = Result<T, E>> + Send + 'static,
+        T: Send + 'static,
+        E: Display + Send + 'static,
+    {
+        let token = token();
+        let future = f(token.clone());
+        let tasks = self.clone();
+        let token_clone = token.clone();
+        // Hold the lock until the handle is stored, so that a task finishing immediately can't remove it before that
+        let mut running = self.0.lock().unwrap();
+        let handle = rt.spawn(async move {
+            let result = future.await;
+            // Whoever removes the handle reports the outcome; if it's gone, the task was cancelled meanwhile
+            if tasks.0.lock().unwrap().remove(&token_clone).is_none() {
+                return;
+            }
+            match result {
+                Ok(_) => ffi::task_done(token_clone),
+                Err(error) => ffi::task_failed(token_clone, error.to_string()),
+            }
+        });
+        running.insert(token.clone(), handle.abort_handle());
+        token
+    }
+
+    /// Abort the task with the given token; it is reported as failed unless it already finished.
+    pub(crate) fn cancel(&self, token: &str) {
+        let Some(handle) = self.0.lock().unwrap().remove(token) else {
+            return;
+        };
+        handle.abort();
+        ffi::task_failed(token.to_string(), "Cancelled".to_string());
+    }
+}
@@ -0,0 +1,63 @@
+// SPDX-FileCopyrightText: 2026 Tobias Fella <tobias.fella@kde.org>
+// SPDX-License-Identifier: LGPL-2.0-or-later
+
+use matrix_sdk_ui::timeline::{EmbeddedEvent, TimelineDetails};
+
+use crate::content_body;
+
+pub struct ThreadSummary {
+    summary: Option<matrix_sdk_ui::timeline::ThreadSummary>,
+    participated: bool,
+}
+
+impl ThreadSummary {
+    pub(crate) fn new(
+        summary: Option<matrix_sdk_ui::timeline::ThreadSummary>,
+        participated: bool,
+    ) -> ThreadSummary {
+        ThreadSummary {
+            summary,
+            participated,
+        }
+    }
+
+    /// Whether the event this summary belongs to is the root of a thread.
+    pub fn is_thread_root(&self) -> bool {
+        self.summary.is_some()
+    }
+
+    pub fn num_replies(&self) -> u32 {
+        self.summary
+            .as_ref()
+            .map(|summary| summary.num_replies)
+            .unwrap_or_default()
+    }
+
+    /// Whether the local user has sent a message in this thread.
+    ///
+    /// This is the server's snapshot from when the thread root was loaded; replies sent since then aren't included.
+    pub fn participated(&self) -> bool {
+        self.participated
+    }
+
+    /// Sender of the latest reply in the thread.
+    pub fn latest_sender(&self) -> String {
+        self.latest_event()
+            .map(|event| event.sender.to_string())
+            .unwrap_or_default()
+    }
+
+    /// Preview of the body of the latest reply in the thread.
+    pub fn latest_body(&self) -> String {
+        self.latest_event()
+            .map(|event| content_body(&event.content))
+            .unwrap_or_default()
+    }
+
+    fn latest_event(&self) -> Option<&EmbeddedEvent> {
+        match &self.summary.as_ref()?.latest_event {
+            TimelineDetails::Ready(event) => Some(event),
+            _ => None,
+        }
+    }
+}
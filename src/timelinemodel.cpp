@@ -3,6 +3,8 @@
 
 #include "timelinemodel.h"
 
+#include <QDateTime>
+
 #include "dispatcher.h"
 #include "ffi.rs.h"
 #include "utils.h"
@@ -30,6 +32,8 @@ public:
     QPointer<Room> room;
     std::optional<rust::Box<sdk::Timeline>> timeline;
     QList<TimelineItemWrapper *> items;
+    // 0: Idle, 1: Paginating, 2: Reached the start of the timeline
+    std::uint8_t backPaginationStatus = 0;
 };
 
 TimelineModel::~TimelineModel() = default;
@@ -41,12 +45,14 @@ TimelineModel::TimelineModel(QObject *parent)
     connect(this, &TimelineModel::roomChanged, this, [this]() {
         if (d->connection) {
             d->timeline = d->connection->connection()->timeline(stringToRust(room()->id()));
+            d->backPaginationStatus = (*d->timeline)->back_pagination_status();
         }
     });
 
     connect(this, &TimelineModel::connectionChanged, this, [this]() {
         if (d->room) {
             d->timeline = d->connection->connection()->timeline(stringToRust(room()->id()));
+            d->backPaginationStatus = (*d->timeline)->back_pagination_status();
         }
     });
     connect(Dispatcher::instance(),
@@ -58,6 +64,19 @@ TimelineModel::TimelineModel(QObject *parent)
                 }
                 timelineUpdate();
             });
+    connect(Dispatcher::instance(),
+            &Dispatcher::backPaginationStatusChanged,
+            this,
+            [this](const auto &matrixId, const auto &roomId, const auto &timelineId, std::uint8_t status) {
+                if (!d->connection || !room() || matrixId != d->connection->matrixId() || roomId != room()->id()) {
+                    return;
+                }
+                // Other timelines of the same room, e.g. threads, paginate independently
+                if (!d->timeline || timelineId != stringFromRust((*d->timeline)->id())) {
+                    return;
+                }
+                d->backPaginationStatus = status;
+            });
 }
 
 Connection *TimelineModel::connection() const
@@ -94,6 +113,8 @@ QHash<int, QByteArray> TimelineModel::roleNames() const
         {TimelineModel::IdRole, "eventId"},
         {TimelineModel::BodyRole, "body"},
         {TimelineModel::TimestampRole, "timestamp"},
+        {TimelineModel::SendStateRole, "sendState"},
+        {TimelineModel::KindRole, "kind"},
     };
 }
 
@@ -109,7 +130,15 @@ QVariant TimelineModel::data(const QModelIndex &index, int role) const
         return stringFromRust((*d->items[row]->item)->body());
     }
     if (role == TimestampRole) {
-        return stringFromRust((*d->items[row]->item)->timestamp());
+        // 0 means the item has no timestamp
+        const auto timestamp = (*d->items[row]->item)->timestamp();
+        return timestamp > 0 ? QDateTime::fromMSecsSinceEpoch(timestamp) : QDateTime();
+    }
+    if (role == KindRole) {
+        return (*d->items[row]->item)->kind();
+    }
+    if (role == SendStateRole) {
+        return (*d->items[row]->item)->send_state();
     }
     return {};
 }
@@ -214,13 +243,13 @@ void TimelineModel::timelineUpdate()
 
 bool TimelineModel::canFetchMore(const QModelIndex &) const
 {
-    return room();
+    return room() && d->backPaginationStatus != 2;
 }
 
 void TimelineModel::fetchMore(const QModelIndex &)
 {
     if (room()) {
-        d->connection->connection()->timeline_paginate_back(**d->timeline);
+        d->connection->connection()->timeline_paginate_back(**d->timeline, 20);
     }
 }
 
@@ -235,7 +264,26 @@ bool ReversedTimelineModel::lessThan(const QModelIndex &sourceLeft, const QModel
     return sourceLeft.row() > sourceRight.row();
 }
 
-void TimelineModel::sendMessage(const QString &message)
+Task *TimelineModel::sendMessage(const QString &message)
+{
+    const auto token = (*d->timeline)->send_message(*d->connection->connection(), stringToRust(message));
+    return new Task(stringFromRust(token), this);
+}
+
+Task *TimelineModel::retrySend(int row)
+{
+    const auto token = (*d->timeline)->retry_send(*d->connection->connection(), **d->items[row]->item);
+    return new Task(stringFromRust(token), this);
+}
+
+Task *TimelineModel::cancelSend(int row)
+{
+    const auto token = (*d->timeline)->cancel_send(*d->connection->connection(), **d->items[row]->item);
+    return new Task(stringFromRust(token), this);
+}
+
+Task *TimelineModel::redact(int row, const QString &reason)
 {
-    (*d->timeline)->send_message(*d->connection->connection(), stringToRust(message));
+    const auto token = (*d->timeline)->redact(*d->connection->connection(), **d->items[row]->item, stringToRust(reason));
+    return new Task(stringFromRust(token), this);
 }
@@ -9,6 +9,7 @@
 #include <qqmlintegration.h>
 
 #include "room.h"
+#include "sdk/src/task.h"
 
 namespace Quotient
 {
@@ -27,6 +28,8 @@ public:
         IdRole = Qt::DisplayRole,
         BodyRole,
         TimestampRole,
+        SendStateRole,
+        KindRole,
     };
     Q_ENUM(RoleNames);
 
@@ -46,7 +49,10 @@ public:
     Quotient::Connection *connection() const;
     void setConnection(Quotient::Connection *connection);
 
-    Q_INVOKABLE void sendMessage(const QString &message);
+    Q_INVOKABLE Task *sendMessage(const QString &message);
+    Q_INVOKABLE Task *retrySend(int row);
+    Q_INVOKABLE Task *cancelSend(int row);
+    Q_INVOKABLE Task *redact(int row, const QString &reason = {});
 
 Q_SIGNALS:
     void roomChanged();